@@ -0,0 +1,167 @@
+use anyhow::Context;
+use anyhow::Result;
+use clap::Parser;
+use datagen::column::decimal::Decimal;
+use datagen::column::key::{JoinKey, Side};
+use datagen::column::Nas;
+use datagen::io::config::Config;
+use datagen::table::Table;
+use datagen::utils::rand::{init as init_rand, split as split_seed};
+use indicatif::ProgressBar;
+use std::path;
+use std::time;
+
+/// Rust program to generate H2O join dataset.
+#[derive(Parser, Debug)]
+#[command(author, version, about, long_about = None)]
+struct Args {
+    /// Number of rows of the LHS table
+    #[arg(short = 'N', long, value_parser = clap::value_parser!(u32).range(1..))]
+    number_of_rows: u32,
+    /// N/A ratio
+    #[arg(short = 'n', long, default_value_t = 0, value_parser = clap::value_parser!(u32).range(0..100))]
+    nas_ratio: u32,
+    /// Output directory
+    #[arg(short, long, default_value_t = String::from("./"))]
+    dir: String,
+    /// Random seed
+    #[arg(long)]
+    seed: Option<u64>,
+    /// Number of threads
+    #[arg(short, long, default_value_t = 1, value_parser = clap::value_parser!(u32).range(1..))]
+    threads: u32,
+    /// Shard index (0-based) of this process
+    #[arg(long, default_value_t = 0)]
+    shard: u32,
+    /// Number of shards the dataset is split into
+    #[arg(long, default_value_t = 1, value_parser = clap::value_parser!(u32).range(1..))]
+    shards: u32,
+}
+
+struct Keys {
+    cardinality: u64,
+    seed: u64,
+}
+
+fn main() -> Result<()> {
+    env_logger::init();
+    let args = Args::parse();
+
+    let seed = args.seed.unwrap_or_else(|| init_rand().0);
+
+    log::info!(
+        "number of rows: {}, NAs ratio: {}, Seed: {}",
+        args.number_of_rows,
+        args.nas_ratio,
+        seed
+    );
+
+    let start = time::Instant::now();
+
+    let n = args.number_of_rows as u64;
+    let keys: Vec<_> = [n / 1_000_000, n / 1_000, n]
+        .into_iter()
+        .enumerate()
+        .map(|(i, cardinality)| Keys {
+            cardinality: cardinality.max(1),
+            seed: split_seed(seed, &format!("id{}", i + 1)),
+        })
+        .collect();
+
+    create_table("NA", n, Side::Left, &keys, seed, &args)
+        .context("failed to create the x table")?;
+    create_table(
+        &format!("{:e}", keys[0].cardinality),
+        keys[0].cardinality,
+        Side::Right,
+        &keys[..1],
+        seed,
+        &args,
+    )
+    .context("failed to create the small table")?;
+    create_table(
+        &format!("{:e}", keys[1].cardinality),
+        keys[1].cardinality,
+        Side::Right,
+        &keys[..2],
+        seed,
+        &args,
+    )
+    .context("failed to create the medium table")?;
+    create_table(
+        &format!("{:e}", keys[2].cardinality),
+        keys[2].cardinality,
+        Side::Right,
+        &keys[..3],
+        seed,
+        &args,
+    )
+    .context("failed to create the big table")?;
+
+    let end = start.elapsed();
+    log::info!(
+        "Elapsed time: {}.{:03} [sec]",
+        end.as_secs(),
+        end.subsec_millis()
+    );
+
+    Ok(())
+}
+
+fn create_table(
+    label: &str,
+    number_of_rows: u64,
+    side: Side,
+    keys: &[Keys],
+    seed: u64,
+    args: &Args,
+) -> Result<()> {
+    let name = format!(
+        "J1_{:e}_{}_{}_0",
+        args.number_of_rows, label, args.nas_ratio
+    );
+    log::info!("Creating {} table (this may take a while)...", name);
+    let mut ids = vec![];
+    let mut labels = vec![];
+    for (i, key) in keys.iter().enumerate() {
+        let id = JoinKey::new(&format!("id{}", i + 1), key.cardinality, side, key.seed)
+            .nas(Nas::ByIndex(args.nas_ratio));
+        labels.push(
+            id.clone()
+                .prefix("id")
+                .rename(&format!("id{}", i + keys.len() + 1)),
+        );
+        ids.push(id);
+    }
+    let table = ids.into_iter().chain(labels).fold(
+        Table::new(&name, number_of_rows as usize)
+            .seed(split_seed(seed, &name))
+            .threads(args.threads as usize)
+            .shard(args.shard as usize, args.shards as usize),
+        Table::column,
+    );
+    let v = if side == Side::Left { "v1" } else { "v2" };
+    let table = table.column(
+        Decimal::new(v, 9, 6)
+            .range(0..100_000_001)
+            .nas(Nas::ByIndex(args.nas_ratio)),
+    );
+
+    let mut path = path::PathBuf::new();
+    path.push(&args.dir);
+    if args.shards > 1 {
+        path.push(format!("{}.part{}.csv", name, args.shard));
+    } else {
+        path.push(format!("{}.csv", name));
+    }
+    let config = Config::new(&path.to_string_lossy()).no_headers(false);
+    let progress = ProgressBar::new((table.rows().len() * table.columns().len()) as u64);
+    let bar = progress.clone();
+    table
+        .progress(move |cells| bar.inc(cells))
+        .write(&config)
+        .with_context(|| format!("failed to write the {} table", name))?;
+    progress.finish();
+    log::info!("Created {} table...", name);
+    Ok(())
+}
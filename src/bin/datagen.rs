@@ -0,0 +1,99 @@
+use anyhow::Context;
+use anyhow::Result;
+use clap::Parser;
+use datagen::io::config::Config;
+use datagen::schema::Schema;
+use datagen::utils::rand::{init as init_rand, split as split_seed};
+use std::path;
+use std::time;
+
+/// Rust program to generate datasets described by a schema file.
+#[derive(Parser, Debug)]
+#[command(author, version, about, long_about = None)]
+struct Args {
+    /// Schema file (TOML)
+    schema: String,
+    /// Output directory
+    #[arg(short, long, default_value_t = String::from("./"))]
+    dir: String,
+    /// Random seed
+    #[arg(long)]
+    seed: Option<u64>,
+    /// Number of threads
+    #[arg(short, long, default_value_t = 1, value_parser = clap::value_parser!(u32).range(1..))]
+    threads: u32,
+    /// Shard index (0-based) of this process
+    #[arg(long, default_value_t = 0)]
+    shard: u32,
+    /// Number of shards the dataset is split into
+    #[arg(long, default_value_t = 1, value_parser = clap::value_parser!(u32).range(1..))]
+    shards: u32,
+}
+
+fn shard_output(output: &str, shard: u32, shards: u32) -> String {
+    if output == "-" || shards <= 1 {
+        return output.to_string();
+    }
+    let (dir, file) = output
+        .rsplit_once('/')
+        .map_or(("", output), |(d, f)| (d, f));
+    let (stem, extensions) = file.split_once('.').unwrap_or((file, "csv"));
+    let file = format!("{}.part{}.{}", stem, shard, extensions);
+    if dir.is_empty() {
+        file
+    } else {
+        format!("{}/{}", dir, file)
+    }
+}
+
+fn main() -> Result<()> {
+    env_logger::init();
+    let args = Args::parse();
+
+    let seed = args.seed.unwrap_or_else(|| init_rand().0);
+    log::info!("schema: {}, seed: {}", args.schema, seed);
+
+    let schema = Schema::from_path(&args.schema).context("failed to load the schema")?;
+    for table_schema in &schema.tables {
+        log::info!(
+            "table: {}, number of rows: {}, sort keys: {:?}",
+            table_schema.name,
+            table_schema.rows,
+            table_schema.sort
+        );
+        let start = time::Instant::now();
+
+        let output = shard_output(&table_schema.output(), args.shard, args.shards);
+        let config = if output == "-" {
+            Config::new(&output)
+        } else {
+            let mut path = path::PathBuf::new();
+            path.push(&args.dir);
+            path.push(&output);
+            Config::new(&path.to_string_lossy())
+        }
+        .no_headers(!table_schema.headers)
+        .quote_style(csv::QuoteStyle::Necessary)
+        .double_quote(true);
+
+        log::info!("Dumping {} table...", table_schema.name);
+        table_schema
+            .table()
+            .with_context(|| format!("invalid definition of the {} table", table_schema.name))?
+            .seed(split_seed(seed, &table_schema.name))
+            .threads(args.threads as usize)
+            .shard(args.shard as usize, args.shards as usize)
+            .write(&config)
+            .with_context(|| format!("failed to create the {} table", table_schema.name))?;
+        log::info!("Dumped {} table...", table_schema.name);
+
+        let end = start.elapsed();
+        log::info!(
+            "Elapsed time: {}.{:03} [sec]",
+            end.as_secs(),
+            end.subsec_millis()
+        );
+    }
+
+    Ok(())
+}
@@ -0,0 +1,281 @@
+use crate::iter::extensions::{choose_range_seeded, KeySet, OptionalIterator};
+use crate::utils::rand::distribution::{KeySampler, Skew};
+use crate::utils::rand::{split as split_seed, split_bytes, Philox};
+use serde::Deserialize;
+use std::hash::{Hash, Hasher};
+use std::io;
+use std::ops::Range;
+
+pub mod decimal;
+pub mod dependent;
+pub mod float;
+pub mod id;
+pub mod integer;
+pub mod key;
+pub mod number;
+pub mod string;
+pub mod temporal;
+pub mod uid;
+
+const MAX_NA_VALUES: u128 = 1 << 16;
+
+pub type Cells<'a> = Box<dyn Iterator<Item = Option<String>> + 'a>;
+
+pub trait CellSource: Send + Sync {
+    fn cells(&self, rows: Range<usize>) -> Cells<'_>;
+}
+
+pub trait ColumnGenerator: Send + Sync {
+    fn name(&self) -> &str;
+
+    fn stream(&self) -> &str {
+        self.name()
+    }
+
+    fn source(&self, seed: u64, number_of_rows: usize) -> Box<dyn CellSource + '_>;
+}
+
+pub trait Latent: Send + Sync {
+    fn value(&self, row: usize) -> f64;
+
+    fn mean_variance(&self) -> (f64, f64);
+
+    fn cdf(&self, x: f64) -> f64;
+
+    fn is_discrete(&self) -> bool;
+}
+
+pub trait LatentColumn: ColumnGenerator {
+    fn latent(&self, seed: u64) -> Box<dyn Latent + '_>;
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Nas {
+    #[default]
+    None,
+    ByValue(u32),
+    ByIndex(u32),
+    WithProbability(u32),
+}
+
+impl Nas {
+    pub fn validate(&self) -> io::Result<()> {
+        match *self {
+            Nas::ByValue(ratio) | Nas::ByIndex(ratio) | Nas::WithProbability(ratio)
+                if ratio > 100 =>
+            {
+                let msg = format!("N/A ratio must be at most 100: {}", ratio);
+                Err(io::Error::new(io::ErrorKind::InvalidInput, msg))
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+pub(crate) enum NaSet<V> {
+    None,
+    Values(KeySet<V>),
+    Sample {
+        amount: usize,
+        total: usize,
+        seed: u64,
+    },
+    Probability(f64, u64),
+    Hashed(u128, u64),
+}
+
+struct SeededHasher(u64);
+
+impl Hasher for SeededHasher {
+    fn finish(&self) -> u64 {
+        self.0
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        self.0 = split_bytes(self.0, bytes);
+    }
+}
+
+fn seeded_hash<V: Hash>(value: &V, seed: u64) -> u64 {
+    let mut hasher = SeededHasher(seed);
+    value.hash(&mut hasher);
+    hasher.finish()
+}
+
+impl<V> NaSet<V>
+where
+    V: Clone + Eq + Hash,
+{
+    pub(crate) fn by_index(number_of_rows: usize, ratio: u32, seed: u64) -> NaSet<V> {
+        let amount = number_of_rows as u64 * ratio as u64 / 100;
+        NaSet::Sample {
+            amount: amount as usize,
+            total: number_of_rows,
+            seed,
+        }
+    }
+
+    pub(crate) fn with_probability(ratio: u32, seed: u64) -> NaSet<V> {
+        NaSet::Probability(ratio as f64 / 100.0, seed)
+    }
+
+    pub(crate) fn by_hash(ratio: u32, seed: u64) -> NaSet<V> {
+        NaSet::Hashed(((ratio as u128) << 64) / 100, seed)
+    }
+
+    pub(crate) fn by_value<F>(
+        size: u128,
+        number_of_rows: usize,
+        ratio: u32,
+        seed: u64,
+        value: F,
+    ) -> NaSet<V>
+    where
+        F: Fn(u64) -> V,
+    {
+        let amount = size.saturating_mul(ratio as u128) / 100;
+        if size <= u64::MAX as u128 && amount <= MAX_NA_VALUES.min(number_of_rows as u128) {
+            NaSet::Values(
+                choose_range_seeded(0..size as u64, amount as usize, seed)
+                    .into_iter()
+                    .map(value)
+                    .collect(),
+            )
+        } else {
+            NaSet::by_hash(ratio, seed)
+        }
+    }
+
+    pub(crate) fn hashed(nas: Nas, number_of_rows: usize, seed: u64) -> NaSet<V> {
+        match nas {
+            Nas::ByValue(ratio) if ratio > 0 => NaSet::by_hash(ratio, seed),
+            Nas::ByIndex(ratio) if ratio > 0 => NaSet::by_index(number_of_rows, ratio, seed),
+            Nas::WithProbability(ratio) if ratio > 0 => NaSet::with_probability(ratio, seed),
+            _ => NaSet::None,
+        }
+    }
+
+    pub(crate) fn apply<'a, I>(
+        &'a self,
+        rows: Range<usize>,
+        values: I,
+    ) -> Box<dyn Iterator<Item = Option<V>> + 'a>
+    where
+        I: Iterator<Item = V> + 'a,
+    {
+        match self {
+            NaSet::None => Box::new(values.map(Some)),
+            NaSet::Values(nas) => Box::new(values.none_by_value(nas.clone())),
+            NaSet::Sample {
+                amount,
+                total,
+                seed,
+            } => Box::new(values.none_by_sample_from(*amount, *total, *seed, rows.start)),
+            NaSet::Probability(probability, seed) => {
+                Box::new(values.none_with_probability_from(*probability, *seed, rows.start))
+            }
+            NaSet::Hashed(threshold, seed) => Box::new(values.map(move |v| {
+                if (seeded_hash(&v, *seed) as u128) < *threshold {
+                    None
+                } else {
+                    Some(v)
+                }
+            })),
+        }
+    }
+}
+
+pub(crate) struct KeyLatent {
+    rand: Philox,
+    sampler: KeySampler,
+}
+
+impl KeyLatent {
+    pub(crate) fn new(seed: u64, domain: Range<u64>, skew: Skew) -> KeyLatent {
+        KeyLatent {
+            rand: Philox::new(split_seed(seed, "values")),
+            sampler: skew.sampler(domain),
+        }
+    }
+}
+
+impl Latent for KeyLatent {
+    fn value(&self, row: usize) -> f64 {
+        self.sampler.sample(&mut self.rand.row(row as u64)) as f64
+    }
+
+    fn mean_variance(&self) -> (f64, f64) {
+        self.sampler.mean_variance()
+    }
+
+    fn cdf(&self, x: f64) -> f64 {
+        if x < 0.0 {
+            0.0
+        } else {
+            self.sampler.cdf(x.floor() as u64)
+        }
+    }
+
+    fn is_discrete(&self) -> bool {
+        true
+    }
+}
+
+pub(crate) struct Discrete<F> {
+    rand: Philox,
+    domain: Range<u64>,
+    sampler: KeySampler,
+    nas: NaSet<u64>,
+    format: F,
+}
+
+impl<F> Discrete<F>
+where
+    F: Fn(u64) -> String + Send + Sync,
+{
+    pub(crate) fn new(
+        seed: u64,
+        number_of_rows: usize,
+        domain: Range<u64>,
+        skew: Skew,
+        nas: Nas,
+        format: F,
+    ) -> Discrete<F> {
+        let na_seed = split_seed(seed, "nas");
+        let mut discrete = Discrete {
+            rand: Philox::new(split_seed(seed, "values")),
+            sampler: skew.sampler(domain.clone()),
+            domain,
+            nas: NaSet::None,
+            format,
+        };
+        discrete.nas = match nas {
+            Nas::ByValue(ratio) if ratio > 0 => {
+                let start = discrete.domain.start;
+                let cardinality = discrete.domain.end.saturating_sub(start);
+                NaSet::by_value(cardinality as u128, number_of_rows, ratio, na_seed, |v| {
+                    start + v
+                })
+            }
+            Nas::ByIndex(ratio) if ratio > 0 => NaSet::by_index(number_of_rows, ratio, na_seed),
+            Nas::WithProbability(ratio) if ratio > 0 => NaSet::with_probability(ratio, na_seed),
+            _ => NaSet::None,
+        };
+        discrete
+    }
+
+    fn value(&self, i: usize) -> u64 {
+        self.sampler.sample(&mut self.rand.row(i as u64))
+    }
+}
+
+impl<F> CellSource for Discrete<F>
+where
+    F: Fn(u64) -> String + Send + Sync,
+{
+    fn cells(&self, rows: Range<usize>) -> Cells<'_> {
+        let values = rows.clone().map(|i| self.value(i));
+        Box::new(self.nas.apply(rows, values).map(|v| v.map(&self.format)))
+    }
+}
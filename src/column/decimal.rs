@@ -0,0 +1,173 @@
+use crate::column::{CellSource, Cells, ColumnGenerator, NaSet, Nas};
+use crate::utils::rand::{split as split_seed, Philox, Rand, RandRange};
+use std::io;
+use std::ops::Range;
+
+pub const MAX_PRECISION: u32 = 38;
+
+fn invalid_decimal(msg: String) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidInput, msg)
+}
+
+pub fn unscaled(s: &str, scale: u32) -> io::Result<i128> {
+    let (negative, digits) = match s.strip_prefix('-') {
+        Some(digits) => (true, digits),
+        None => (false, s.strip_prefix('+').unwrap_or(s)),
+    };
+    let (integer, fraction) = digits.split_once('.').unwrap_or((digits, ""));
+    let valid = !(integer.is_empty() && fraction.is_empty())
+        && integer
+            .bytes()
+            .chain(fraction.bytes())
+            .all(|b| b.is_ascii_digit())
+        && fraction.len() <= scale as usize;
+    if !valid {
+        return Err(invalid_decimal(format!(
+            "invalid decimal with scale {}: {}",
+            scale, s
+        )));
+    }
+    let mut value: i128 = 0;
+    let padding = scale as usize - fraction.len();
+    for b in integer
+        .bytes()
+        .chain(fraction.bytes())
+        .chain((0..padding).map(|_| b'0'))
+    {
+        value = value
+            .checked_mul(10)
+            .and_then(|v| v.checked_add((b - b'0') as i128))
+            .ok_or_else(|| invalid_decimal(format!("decimal out of range: {}", s)))?;
+    }
+    Ok(if negative { -value } else { value })
+}
+
+#[derive(Clone, Debug)]
+pub struct Decimal {
+    name: String,
+    precision: u32,
+    scale: u32,
+    range: Range<i128>,
+    nas: Nas,
+}
+
+impl Decimal {
+    pub fn new(name: &str, precision: u32, scale: u32) -> Decimal {
+        let precision = precision.clamp(1, MAX_PRECISION);
+        let limit = 10i128.pow(precision);
+        Decimal {
+            name: name.to_string(),
+            precision,
+            scale: scale.min(precision),
+            range: -limit + 1..limit,
+            nas: Nas::None,
+        }
+    }
+
+    pub fn range(mut self, range: Range<i128>) -> Decimal {
+        self.range = range;
+        self
+    }
+
+    pub fn nas(mut self, nas: Nas) -> Decimal {
+        self.nas = nas;
+        self
+    }
+
+    pub fn validate(&self) -> io::Result<()> {
+        let limit = 10i128.pow(self.precision);
+        if self.range.start < self.range.end && self.range.start > -limit && self.range.end <= limit
+        {
+            Ok(())
+        } else {
+            Err(invalid_decimal(format!(
+                "range of {} does not fit DECIMAL({}, {})",
+                self.name, self.precision, self.scale
+            )))
+        }
+    }
+
+    fn render(&self, v: i128) -> String {
+        let divisor = 10u128.pow(self.scale);
+        let sign = if v < 0 { "-" } else { "" };
+        let (integer, fraction) = (v.unsigned_abs() / divisor, v.unsigned_abs() % divisor);
+        if self.scale == 0 {
+            format!("{}{}", sign, integer)
+        } else {
+            format!(
+                "{}{}.{:0scale$}",
+                sign,
+                integer,
+                fraction,
+                scale = self.scale as usize
+            )
+        }
+    }
+}
+
+fn next_below<R: Rand>(rand: &mut R, n: u128) -> u128 {
+    if n <= u64::MAX as u128 {
+        return rand.next_range(0..n as u64) as u128;
+    }
+    let limit = u128::MAX - u128::MAX % n;
+    loop {
+        let v = (rand.next_u64() as u128) << 64 | rand.next_u64() as u128;
+        if v < limit {
+            return v % n;
+        }
+    }
+}
+
+struct DecimalSource<'a> {
+    column: &'a Decimal,
+    rand: Philox,
+    nas: NaSet<i128>,
+}
+
+impl DecimalSource<'_> {
+    fn value(&self, i: usize) -> i128 {
+        let range = &self.column.range;
+        let n = range.end.abs_diff(range.start);
+        range
+            .start
+            .wrapping_add(next_below(&mut self.rand.row(i as u64), n) as i128)
+    }
+}
+
+impl CellSource for DecimalSource<'_> {
+    fn cells(&self, rows: Range<usize>) -> Cells<'_> {
+        let values = rows.clone().map(|i| self.value(i));
+        Box::new(
+            self.nas
+                .apply(rows, values)
+                .map(|v| v.map(|v| self.column.render(v))),
+        )
+    }
+}
+
+impl ColumnGenerator for Decimal {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn source(&self, seed: u64, number_of_rows: usize) -> Box<dyn CellSource + '_> {
+        let na_seed = split_seed(seed, "nas");
+        let nas = match self.nas {
+            Nas::ByValue(ratio) if ratio > 0 => {
+                let start = self.range.start;
+                let size = self.range.end.abs_diff(start);
+                NaSet::by_value(size, number_of_rows, ratio, na_seed, |offset| {
+                    start.wrapping_add(offset as i128)
+                })
+            }
+            Nas::ByIndex(ratio) if ratio > 0 => NaSet::by_index(number_of_rows, ratio, na_seed),
+            Nas::WithProbability(ratio) if ratio > 0 => NaSet::with_probability(ratio, na_seed),
+            _ => NaSet::None,
+        };
+        Box::new(DecimalSource {
+            column: self,
+            rand: Philox::new(split_seed(seed, "values")),
+            nas,
+        })
+    }
+}
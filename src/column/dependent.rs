@@ -0,0 +1,249 @@
+use crate::column::{CellSource, Cells, ColumnGenerator, Latent, LatentColumn, NaSet, Nas};
+use crate::utils::rand::distribution::Distribution;
+use crate::utils::rand::{split as split_seed, Philox};
+use hashbrown::HashMap;
+use serde::Deserialize;
+use std::io;
+use std::ops::Range;
+use std::sync::Arc;
+
+const STANDARD_NORMAL: Distribution = Distribution::Normal {
+    mean: 0.0,
+    std_dev: 1.0,
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, Deserialize)]
+pub struct Case {
+    pub key: u64,
+    pub distribution: Distribution,
+}
+
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Dependence {
+    Conditional {
+        #[serde(default)]
+        cases: Vec<Case>,
+        default: Distribution,
+    },
+    Linear {
+        coefficient: f64,
+        mean: f64,
+        std_dev: f64,
+    },
+    Copula {
+        coefficient: f64,
+        distribution: Distribution,
+    },
+}
+
+impl Dependence {
+    pub fn validate(&self) -> io::Result<()> {
+        let coefficient = match self {
+            Dependence::Conditional { cases, default } => {
+                default.validate()?;
+                for case in cases {
+                    case.distribution.validate()?;
+                }
+                0.0
+            }
+            Dependence::Linear {
+                coefficient,
+                std_dev,
+                ..
+            } if *std_dev >= 0.0 => *coefficient,
+            Dependence::Linear { .. } => f64::NAN,
+            Dependence::Copula {
+                coefficient,
+                distribution,
+            } => {
+                distribution.validate()?;
+                *coefficient
+            }
+        };
+        if (-1.0..=1.0).contains(&coefficient) {
+            Ok(())
+        } else {
+            let msg = format!("invalid dependence parameters: {:?}", self);
+            Err(io::Error::new(io::ErrorKind::InvalidInput, msg))
+        }
+    }
+
+    fn is_discrete(&self) -> bool {
+        match self {
+            Dependence::Conditional { cases, default } => {
+                default.is_discrete() && cases.iter().all(|c| c.distribution.is_discrete())
+            }
+            Dependence::Linear { .. } => false,
+            Dependence::Copula { distribution, .. } => distribution.is_discrete(),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct Dependent {
+    name: String,
+    parent: Arc<dyn LatentColumn>,
+    dependence: Dependence,
+    precision: usize,
+    nas: Nas,
+}
+
+impl Dependent {
+    pub fn new<C>(name: &str, parent: C, dependence: Dependence) -> Dependent
+    where
+        C: LatentColumn + 'static,
+    {
+        Dependent::boxed(name, Box::new(parent), dependence)
+    }
+
+    pub fn boxed(name: &str, parent: Box<dyn LatentColumn>, dependence: Dependence) -> Dependent {
+        Dependent {
+            name: name.to_string(),
+            parent: Arc::from(parent),
+            dependence,
+            precision: 6,
+            nas: Nas::None,
+        }
+    }
+
+    pub fn precision(mut self, precision: usize) -> Dependent {
+        self.precision = precision;
+        self
+    }
+
+    pub fn nas(mut self, nas: Nas) -> Dependent {
+        self.nas = nas;
+        self
+    }
+}
+
+enum Relation {
+    Conditional(HashMap<u64, Distribution>, Distribution),
+    Linear {
+        shift: f64,
+        scale: f64,
+        coefficient: f64,
+        mean: f64,
+        std_dev: f64,
+    },
+    Copula {
+        coefficient: f64,
+        distribution: Distribution,
+    },
+}
+
+struct DependentSource<'a> {
+    column: &'a Dependent,
+    parent: Box<dyn Latent + 'a>,
+    relation: Relation,
+    rand: Philox,
+    nas: NaSet<String>,
+}
+
+impl DependentSource<'_> {
+    fn score(&self, x: f64) -> f64 {
+        let u = if self.parent.is_discrete() {
+            (self.parent.cdf(x - 1.0) + self.parent.cdf(x)) / 2.0
+        } else {
+            self.parent.cdf(x)
+        };
+        STANDARD_NORMAL.quantile(u.clamp(f64::EPSILON, 1.0 - f64::EPSILON))
+    }
+
+    fn value(&self, i: usize) -> String {
+        let x = self.parent.value(i);
+        let mut rand = self.rand.row(i as u64);
+        let v = match &self.relation {
+            Relation::Conditional(cases, default) => {
+                cases.get(&(x as u64)).unwrap_or(default).sample(&mut rand)
+            }
+            Relation::Linear {
+                shift,
+                scale,
+                coefficient,
+                mean,
+                std_dev,
+            } => {
+                let z = (x - shift) * scale;
+                let noise = STANDARD_NORMAL.sample(&mut rand);
+                mean + std_dev * (coefficient * z + (1.0 - coefficient.powi(2)).sqrt() * noise)
+            }
+            Relation::Copula {
+                coefficient,
+                distribution,
+            } => {
+                let noise = STANDARD_NORMAL.sample(&mut rand);
+                let w = coefficient * self.score(x) + (1.0 - coefficient.powi(2)).sqrt() * noise;
+                distribution.quantile(STANDARD_NORMAL.cdf(w))
+            }
+        };
+        if self.column.dependence.is_discrete() {
+            format!("{}", v as i64)
+        } else {
+            format!("{:.precision$}", v, precision = self.column.precision)
+        }
+    }
+}
+
+impl CellSource for DependentSource<'_> {
+    fn cells(&self, rows: Range<usize>) -> Cells<'_> {
+        let values = rows.clone().map(|i| self.value(i));
+        self.nas.apply(rows, values)
+    }
+}
+
+impl ColumnGenerator for Dependent {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn stream(&self) -> &str {
+        self.parent.stream()
+    }
+
+    fn source(&self, seed: u64, number_of_rows: usize) -> Box<dyn CellSource + '_> {
+        let parent = self.parent.latent(seed);
+        let seed = split_seed(split_seed(seed, "dependents"), &self.name);
+        let relation = match &self.dependence {
+            Dependence::Conditional { cases, default } => Relation::Conditional(
+                cases.iter().map(|c| (c.key, c.distribution)).collect(),
+                *default,
+            ),
+            Dependence::Linear {
+                coefficient,
+                mean,
+                std_dev,
+            } => {
+                let (shift, scale) = match parent.mean_variance() {
+                    (mean, variance) if variance > 0.0 && variance.is_finite() => {
+                        (mean, 1.0 / variance.sqrt())
+                    }
+                    _ => (0.0, 0.0),
+                };
+                Relation::Linear {
+                    shift,
+                    scale,
+                    coefficient: *coefficient,
+                    mean: *mean,
+                    std_dev: *std_dev,
+                }
+            }
+            Dependence::Copula {
+                coefficient,
+                distribution,
+            } => Relation::Copula {
+                coefficient: *coefficient,
+                distribution: *distribution,
+            },
+        };
+        let source = DependentSource {
+            column: self,
+            parent,
+            relation,
+            rand: Philox::new(split_seed(seed, "values")),
+            nas: NaSet::hashed(self.nas, number_of_rows, split_seed(seed, "nas")),
+        };
+        Box::new(source)
+    }
+}
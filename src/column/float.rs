@@ -0,0 +1,63 @@
+use crate::column::{CellSource, ColumnGenerator, Discrete, KeyLatent, Latent, LatentColumn, Nas};
+use crate::utils::rand::distribution::Skew;
+use std::ops::Range;
+
+#[derive(Clone, Debug)]
+pub struct ScaledFloat {
+    name: String,
+    range: Range<u64>,
+    scale: u64,
+    precision: usize,
+    nas: Nas,
+}
+
+impl ScaledFloat {
+    pub fn new(name: &str, range: Range<u64>, scale: u64) -> ScaledFloat {
+        ScaledFloat {
+            name: name.to_string(),
+            range,
+            scale,
+            precision: 6,
+            nas: Nas::None,
+        }
+    }
+
+    pub fn precision(mut self, precision: usize) -> ScaledFloat {
+        self.precision = precision;
+        self
+    }
+
+    pub fn nas(mut self, nas: Nas) -> ScaledFloat {
+        self.nas = nas;
+        self
+    }
+}
+
+impl ColumnGenerator for ScaledFloat {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn source(&self, seed: u64, number_of_rows: usize) -> Box<dyn CellSource + '_> {
+        Box::new(Discrete::new(
+            seed,
+            number_of_rows,
+            self.range.clone(),
+            Skew::Uniform,
+            self.nas,
+            |v| {
+                format!(
+                    "{:.precision$}",
+                    v as f64 / self.scale as f64,
+                    precision = self.precision
+                )
+            },
+        ))
+    }
+}
+
+impl LatentColumn for ScaledFloat {
+    fn latent(&self, seed: u64) -> Box<dyn Latent + '_> {
+        Box::new(KeyLatent::new(seed, self.range.clone(), Skew::Uniform))
+    }
+}
@@ -0,0 +1,69 @@
+use crate::column::{CellSource, ColumnGenerator, Discrete, KeyLatent, Latent, LatentColumn, Nas};
+use crate::utils::rand::distribution::Skew;
+use std::ops::Range;
+
+#[derive(Clone, Debug)]
+pub struct FormattedId {
+    name: String,
+    range: Range<u64>,
+    skew: Skew,
+    prefix: String,
+    width: usize,
+    nas: Nas,
+}
+
+impl FormattedId {
+    pub fn new(name: &str, range: Range<u64>) -> FormattedId {
+        FormattedId {
+            name: name.to_string(),
+            range,
+            skew: Skew::Uniform,
+            prefix: String::from("id"),
+            width: 3,
+            nas: Nas::None,
+        }
+    }
+
+    pub fn prefix(mut self, prefix: &str) -> FormattedId {
+        self.prefix = prefix.to_string();
+        self
+    }
+
+    pub fn width(mut self, width: usize) -> FormattedId {
+        self.width = width;
+        self
+    }
+
+    pub fn skew(mut self, skew: Skew) -> FormattedId {
+        self.skew = skew;
+        self
+    }
+
+    pub fn nas(mut self, nas: Nas) -> FormattedId {
+        self.nas = nas;
+        self
+    }
+}
+
+impl ColumnGenerator for FormattedId {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn source(&self, seed: u64, number_of_rows: usize) -> Box<dyn CellSource + '_> {
+        Box::new(Discrete::new(
+            seed,
+            number_of_rows,
+            self.range.clone(),
+            self.skew,
+            self.nas,
+            |v| format!("{}{:0width$}", self.prefix, v, width = self.width),
+        ))
+    }
+}
+
+impl LatentColumn for FormattedId {
+    fn latent(&self, seed: u64) -> Box<dyn Latent + '_> {
+        Box::new(KeyLatent::new(seed, self.range.clone(), self.skew))
+    }
+}
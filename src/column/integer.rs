@@ -0,0 +1,55 @@
+use crate::column::{CellSource, ColumnGenerator, Discrete, KeyLatent, Latent, LatentColumn, Nas};
+use crate::utils::rand::distribution::Skew;
+use std::ops::Range;
+
+#[derive(Clone, Debug)]
+pub struct RandomInteger {
+    name: String,
+    range: Range<u64>,
+    skew: Skew,
+    nas: Nas,
+}
+
+impl RandomInteger {
+    pub fn new(name: &str, range: Range<u64>) -> RandomInteger {
+        RandomInteger {
+            name: name.to_string(),
+            range,
+            skew: Skew::Uniform,
+            nas: Nas::None,
+        }
+    }
+
+    pub fn skew(mut self, skew: Skew) -> RandomInteger {
+        self.skew = skew;
+        self
+    }
+
+    pub fn nas(mut self, nas: Nas) -> RandomInteger {
+        self.nas = nas;
+        self
+    }
+}
+
+impl ColumnGenerator for RandomInteger {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn source(&self, seed: u64, number_of_rows: usize) -> Box<dyn CellSource + '_> {
+        Box::new(Discrete::new(
+            seed,
+            number_of_rows,
+            self.range.clone(),
+            self.skew,
+            self.nas,
+            |v| v.to_string(),
+        ))
+    }
+}
+
+impl LatentColumn for RandomInteger {
+    fn latent(&self, seed: u64) -> Box<dyn Latent + '_> {
+        Box::new(KeyLatent::new(seed, self.range.clone(), self.skew))
+    }
+}
@@ -0,0 +1,151 @@
+use crate::column::{CellSource, Cells, ColumnGenerator, NaSet, Nas};
+use crate::utils::rand::{
+    rewind as rewind_rand, split as split_seed, Permutation, Philox, RandRange,
+};
+use std::ops::Range;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Side {
+    Left,
+    Right,
+}
+
+#[derive(Clone, Debug)]
+struct Pool {
+    keys: Permutation,
+    overlap: u64,
+    extra: u64,
+    side: Side,
+}
+
+impl Pool {
+    fn new(cardinality: u64, side: Side, seed: u64) -> Pool {
+        let extra = cardinality / 10;
+        Pool {
+            keys: Permutation::new(cardinality + extra, &mut rewind_rand(seed)),
+            overlap: cardinality - extra,
+            extra,
+            side,
+        }
+    }
+
+    fn key(&self, slot: u64) -> u64 {
+        match self.side {
+            Side::Right if slot >= self.overlap => self.keys.apply(slot + self.extra) + 1,
+            _ => self.keys.apply(slot) + 1,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct JoinKey {
+    name: String,
+    stream: String,
+    cardinality: u64,
+    side: Side,
+    key_seed: u64,
+    prefix: Option<String>,
+    nas: Nas,
+}
+
+impl JoinKey {
+    pub fn new(name: &str, cardinality: u64, side: Side, key_seed: u64) -> JoinKey {
+        JoinKey {
+            name: name.to_string(),
+            stream: name.to_string(),
+            cardinality,
+            side,
+            key_seed,
+            prefix: None,
+            nas: Nas::None,
+        }
+    }
+
+    pub fn rename(mut self, name: &str) -> JoinKey {
+        self.name = name.to_string();
+        self
+    }
+
+    pub fn prefix(mut self, prefix: &str) -> JoinKey {
+        self.prefix = Some(prefix.to_string());
+        self
+    }
+
+    pub fn nas(mut self, nas: Nas) -> JoinKey {
+        self.nas = nas;
+        self
+    }
+}
+
+struct JoinKeySource<'a> {
+    column: &'a JoinKey,
+    pool: Pool,
+    shuffle: Permutation,
+    rand: Philox,
+    nas: NaSet<u64>,
+}
+
+impl JoinKeySource<'_> {
+    fn value(&self, i: usize) -> u64 {
+        let i = i as u64;
+        self.pool.key(match self.shuffle.apply(i) {
+            slot if slot < self.column.cardinality => slot,
+            _ => self.rand.row(i).next_range(0..self.column.cardinality),
+        })
+    }
+
+    fn format(&self, v: u64) -> String {
+        match self.column.prefix {
+            Some(ref prefix) => format!("{}{}", prefix, v),
+            None => v.to_string(),
+        }
+    }
+}
+
+impl CellSource for JoinKeySource<'_> {
+    fn cells(&self, rows: Range<usize>) -> Cells<'_> {
+        let values = rows.clone().map(|i| self.value(i));
+        Box::new(
+            self.nas
+                .apply(rows, values)
+                .map(|v| v.map(|v| self.format(v))),
+        )
+    }
+}
+
+impl ColumnGenerator for JoinKey {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn stream(&self) -> &str {
+        &self.stream
+    }
+
+    fn source(&self, seed: u64, number_of_rows: usize) -> Box<dyn CellSource + '_> {
+        let pool = Pool::new(self.cardinality, self.side, self.key_seed);
+        let na_seed = split_seed(seed, "nas");
+        let nas = match self.nas {
+            Nas::ByValue(ratio) if ratio > 0 => NaSet::by_value(
+                self.cardinality as u128,
+                number_of_rows,
+                ratio,
+                na_seed,
+                |slot| pool.key(slot),
+            ),
+            Nas::ByIndex(ratio) if ratio > 0 => NaSet::by_index(number_of_rows, ratio, na_seed),
+            Nas::WithProbability(ratio) if ratio > 0 => NaSet::with_probability(ratio, na_seed),
+            _ => NaSet::None,
+        };
+        Box::new(JoinKeySource {
+            column: self,
+            pool,
+            shuffle: Permutation::new(
+                number_of_rows as u64,
+                &mut rewind_rand(split_seed(seed, "rows")),
+            ),
+            rand: Philox::new(split_seed(seed, "values")),
+            nas,
+        })
+    }
+}
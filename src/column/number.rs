@@ -0,0 +1,107 @@
+use crate::column::{CellSource, Cells, ColumnGenerator, Latent, LatentColumn, NaSet, Nas};
+use crate::utils::rand::distribution::Distribution;
+use crate::utils::rand::{split as split_seed, Philox};
+use std::ops::Range;
+
+#[derive(Clone, Debug)]
+pub struct RandomNumber {
+    name: String,
+    distribution: Distribution,
+    precision: usize,
+    nas: Nas,
+}
+
+impl RandomNumber {
+    pub fn new(name: &str, distribution: Distribution) -> RandomNumber {
+        RandomNumber {
+            name: name.to_string(),
+            distribution,
+            precision: 6,
+            nas: Nas::None,
+        }
+    }
+
+    pub fn precision(mut self, precision: usize) -> RandomNumber {
+        self.precision = precision;
+        self
+    }
+
+    pub fn nas(mut self, nas: Nas) -> RandomNumber {
+        self.nas = nas;
+        self
+    }
+}
+
+struct RandomNumberSource<'a> {
+    column: &'a RandomNumber,
+    rand: Philox,
+    nas: NaSet<String>,
+}
+
+impl RandomNumberSource<'_> {
+    fn value(&self, i: usize) -> String {
+        let v = self
+            .column
+            .distribution
+            .sample(&mut self.rand.row(i as u64));
+        if self.column.distribution.is_discrete() {
+            format!("{}", v as i64)
+        } else {
+            format!("{:.precision$}", v, precision = self.column.precision)
+        }
+    }
+}
+
+impl CellSource for RandomNumberSource<'_> {
+    fn cells(&self, rows: Range<usize>) -> Cells<'_> {
+        let values = rows.clone().map(|i| self.value(i));
+        self.nas.apply(rows, values)
+    }
+}
+
+impl ColumnGenerator for RandomNumber {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn source(&self, seed: u64, number_of_rows: usize) -> Box<dyn CellSource + '_> {
+        let source = RandomNumberSource {
+            column: self,
+            rand: Philox::new(split_seed(seed, "values")),
+            nas: NaSet::hashed(self.nas, number_of_rows, split_seed(seed, "nas")),
+        };
+        Box::new(source)
+    }
+}
+
+struct RandomNumberLatent {
+    distribution: Distribution,
+    rand: Philox,
+}
+
+impl Latent for RandomNumberLatent {
+    fn value(&self, row: usize) -> f64 {
+        self.distribution.sample(&mut self.rand.row(row as u64))
+    }
+
+    fn mean_variance(&self) -> (f64, f64) {
+        (self.distribution.mean(), self.distribution.variance())
+    }
+
+    fn cdf(&self, x: f64) -> f64 {
+        self.distribution.cdf(x)
+    }
+
+    fn is_discrete(&self) -> bool {
+        self.distribution.is_discrete()
+    }
+}
+
+impl LatentColumn for RandomNumber {
+    fn latent(&self, seed: u64) -> Box<dyn Latent + '_> {
+        Box::new(RandomNumberLatent {
+            distribution: self.distribution,
+            rand: Philox::new(split_seed(seed, "values")),
+        })
+    }
+}
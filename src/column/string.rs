@@ -0,0 +1,290 @@
+use crate::column::{CellSource, Cells, ColumnGenerator, NaSet, Nas};
+use crate::utils::rand::distribution::Distribution;
+use crate::utils::rand::{split as split_seed, Philox, Rand, RandRange};
+use serde::Deserialize;
+use std::io;
+use std::ops::Range;
+use std::str::FromStr;
+
+pub const DEFAULT_MAX_LENGTH: usize = 1024;
+
+const UNICODE: [Range<u32>; 3] = [0x20..0x7f, 0xa0..0xd800, 0xe000..0x11_0000];
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Alphabet {
+    Ascii,
+    #[default]
+    Alphanumeric,
+    Lowercase,
+    Uppercase,
+    Digits,
+    Hex,
+    Unicode,
+    Custom(String),
+}
+
+impl Alphabet {
+    pub fn validate(&self) -> io::Result<()> {
+        match self {
+            Alphabet::Custom(chars) if chars.is_empty() => {
+                let msg = "custom alphabet must not be empty";
+                Err(io::Error::new(io::ErrorKind::InvalidInput, msg))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    fn sample<R: Rand>(&self, rand: &mut R) -> char {
+        let pick = |chars: &str, rand: &mut R| {
+            let bytes = chars.as_bytes();
+            bytes[rand.next_range(0..bytes.len())] as char
+        };
+        match self {
+            Alphabet::Ascii => rand.next_range(0x20u32..0x7f) as u8 as char,
+            Alphabet::Alphanumeric => pick(
+                "0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz",
+                rand,
+            ),
+            Alphabet::Lowercase => pick("abcdefghijklmnopqrstuvwxyz", rand),
+            Alphabet::Uppercase => pick("ABCDEFGHIJKLMNOPQRSTUVWXYZ", rand),
+            Alphabet::Digits => pick("0123456789", rand),
+            Alphabet::Hex => pick("0123456789abcdef", rand),
+            Alphabet::Unicode => {
+                let size = UNICODE.iter().map(|r| r.end - r.start).sum::<u32>();
+                let mut k = rand.next_range(0..size);
+                for range in UNICODE.iter() {
+                    if k < range.end - range.start {
+                        return char::from_u32(range.start + k).unwrap_or(' ');
+                    }
+                    k -= range.end - range.start;
+                }
+                unreachable!()
+            }
+            Alphabet::Custom(chars) => {
+                let count = chars.chars().count();
+                chars.chars().nth(rand.next_range(0..count)).unwrap_or(' ')
+            }
+        }
+    }
+}
+
+impl FromStr for Alphabet {
+    type Err = io::Error;
+
+    fn from_str(s: &str) -> io::Result<Alphabet> {
+        match s {
+            "ascii" => Ok(Alphabet::Ascii),
+            "alphanumeric" => Ok(Alphabet::Alphanumeric),
+            "lowercase" => Ok(Alphabet::Lowercase),
+            "uppercase" => Ok(Alphabet::Uppercase),
+            "digits" => Ok(Alphabet::Digits),
+            "hex" => Ok(Alphabet::Hex),
+            "unicode" => Ok(Alphabet::Unicode),
+            _ => {
+                let msg = format!("unknown alphabet: {}", s);
+                Err(io::Error::new(io::ErrorKind::InvalidInput, msg))
+            }
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Part {
+    Literal(String),
+    Class(Alphabet, Range<usize>),
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Template {
+    parts: Vec<Part>,
+}
+
+impl Template {
+    fn max_length(&self) -> usize {
+        self.parts.iter().fold(0, |length, part| match part {
+            Part::Literal(literal) => length.saturating_add(literal.chars().count()),
+            Part::Class(_, range) => length.saturating_add(range.end - 1),
+        })
+    }
+
+    fn render<R: Rand>(&self, rand: &mut R, s: &mut String) {
+        for part in self.parts.iter() {
+            match part {
+                Part::Literal(literal) => s.push_str(literal),
+                Part::Class(alphabet, length) => {
+                    for _ in 0..rand.next_range(length.clone()) {
+                        s.push(alphabet.sample(rand));
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn invalid_template(template: &str) -> io::Error {
+    let msg = format!("invalid template: {}", template);
+    io::Error::new(io::ErrorKind::InvalidInput, msg)
+}
+
+impl FromStr for Template {
+    type Err = io::Error;
+
+    fn from_str(s: &str) -> io::Result<Template> {
+        let mut parts = vec![];
+        let mut literal = String::new();
+        let mut chars = s.chars().peekable();
+        while let Some(c) = chars.next() {
+            match c {
+                '{' if chars.peek() == Some(&'{') => {
+                    chars.next();
+                    literal.push('{');
+                }
+                '}' if chars.peek() == Some(&'}') => {
+                    chars.next();
+                    literal.push('}');
+                }
+                '{' => {
+                    let mut placeholder = String::new();
+                    loop {
+                        match chars.next() {
+                            Some('}') => break,
+                            Some(c) => placeholder.push(c),
+                            None => return Err(invalid_template(s)),
+                        }
+                    }
+                    let (class, length) = match placeholder.split_once(':') {
+                        Some((class, length)) => (class, length),
+                        None => (placeholder.as_str(), "1"),
+                    };
+                    let (min, max) = length.split_once('-').unwrap_or((length, length));
+                    let (min, max) = match (min.parse::<usize>(), max.parse::<usize>()) {
+                        (Ok(min), Ok(max)) if min <= max => (min, max),
+                        _ => return Err(invalid_template(s)),
+                    };
+                    if !literal.is_empty() {
+                        parts.push(Part::Literal(std::mem::take(&mut literal)));
+                    }
+                    let end = max.checked_add(1).ok_or_else(|| invalid_template(s))?;
+                    parts.push(Part::Class(class.parse()?, min..end));
+                }
+                '}' => return Err(invalid_template(s)),
+                c => literal.push(c),
+            }
+        }
+        if !literal.is_empty() {
+            parts.push(Part::Literal(literal));
+        }
+        Ok(Template { parts })
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct RandomString {
+    name: String,
+    length: Distribution,
+    max_length: usize,
+    alphabet: Alphabet,
+    template: Option<Template>,
+    nas: Nas,
+}
+
+impl RandomString {
+    pub fn new(name: &str) -> RandomString {
+        RandomString {
+            name: name.to_string(),
+            length: Distribution::Uniform {
+                low: 8.0,
+                high: 16.0,
+            },
+            max_length: DEFAULT_MAX_LENGTH,
+            alphabet: Alphabet::Alphanumeric,
+            template: None,
+            nas: Nas::None,
+        }
+    }
+
+    pub fn length(mut self, length: Distribution) -> RandomString {
+        self.length = length;
+        self
+    }
+
+    pub fn max_length(mut self, max_length: usize) -> RandomString {
+        self.max_length = max_length;
+        self
+    }
+
+    pub fn alphabet(mut self, alphabet: Alphabet) -> RandomString {
+        self.alphabet = alphabet;
+        self
+    }
+
+    pub fn template(mut self, template: Template) -> RandomString {
+        self.template = Some(template);
+        self
+    }
+
+    pub fn nas(mut self, nas: Nas) -> RandomString {
+        self.nas = nas;
+        self
+    }
+
+    pub fn validate(&self) -> io::Result<()> {
+        match self.template {
+            Some(ref template) if template.max_length() > self.max_length => {
+                let msg = format!(
+                    "template of {} is longer than {} characters",
+                    self.name, self.max_length
+                );
+                Err(io::Error::new(io::ErrorKind::InvalidInput, msg))
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+struct RandomStringSource<'a> {
+    column: &'a RandomString,
+    rand: Philox,
+    nas: NaSet<String>,
+}
+
+impl RandomStringSource<'_> {
+    fn value(&self, i: usize) -> String {
+        let mut rand = self.rand.row(i as u64);
+        let mut s = String::new();
+        match &self.column.template {
+            Some(template) => template.render(&mut rand, &mut s),
+            None => {
+                let length = self.column.length.sample(&mut rand).max(0.0) as usize;
+                let length = length.min(self.column.max_length);
+                for _ in 0..length {
+                    s.push(self.column.alphabet.sample(&mut rand));
+                }
+            }
+        }
+        s
+    }
+}
+
+impl CellSource for RandomStringSource<'_> {
+    fn cells(&self, rows: Range<usize>) -> Cells<'_> {
+        let values = rows.clone().map(|i| self.value(i));
+        self.nas.apply(rows, values)
+    }
+}
+
+impl ColumnGenerator for RandomString {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn source(&self, seed: u64, number_of_rows: usize) -> Box<dyn CellSource + '_> {
+        let source = RandomStringSource {
+            column: self,
+            rand: Philox::new(split_seed(seed, "values")),
+            nas: NaSet::hashed(self.nas, number_of_rows, split_seed(seed, "nas")),
+        };
+        Box::new(source)
+    }
+}
@@ -0,0 +1,285 @@
+use crate::column::{CellSource, ColumnGenerator, Discrete, KeyLatent, Latent, LatentColumn, Nas};
+use crate::utils::rand::distribution::Skew;
+use chrono::format::{Item, StrftimeItems};
+use chrono::{DateTime, NaiveDate, NaiveTime, SecondsFormat, TimeDelta, Timelike};
+use chrono_tz::Tz;
+use serde::Deserialize;
+use std::fmt::Write;
+use std::io;
+use std::ops::Range;
+
+const MILLIS_PER_DAY: i64 = 86_400_000;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TemporalKind {
+    Date,
+    Time,
+    Timestamp,
+    Interval,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Granularity {
+    Millisecond,
+    Second,
+    Minute,
+    Hour,
+    Day,
+}
+
+impl Granularity {
+    pub fn millis(&self) -> i64 {
+        match self {
+            Granularity::Millisecond => 1,
+            Granularity::Second => 1_000,
+            Granularity::Minute => 60_000,
+            Granularity::Hour => 3_600_000,
+            Granularity::Day => MILLIS_PER_DAY,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TemporalFormat {
+    #[default]
+    Iso8601,
+    EpochSeconds,
+    EpochMillis,
+    Pattern(String),
+}
+
+#[derive(Clone, Debug)]
+pub struct Temporal {
+    name: String,
+    kind: TemporalKind,
+    range: Range<i64>,
+    granularity: Granularity,
+    time_zone: Tz,
+    format: TemporalFormat,
+    nas: Nas,
+}
+
+impl Temporal {
+    fn new(
+        name: &str,
+        kind: TemporalKind,
+        range: Range<i64>,
+        granularity: Granularity,
+    ) -> Temporal {
+        Temporal {
+            name: name.to_string(),
+            kind,
+            range,
+            granularity,
+            time_zone: Tz::UTC,
+            format: TemporalFormat::Iso8601,
+            nas: Nas::None,
+        }
+    }
+
+    pub fn date(name: &str, range: Range<NaiveDate>) -> Temporal {
+        let millis = |d: NaiveDate| d.and_time(NaiveTime::MIN).and_utc().timestamp_millis();
+        Temporal::new(
+            name,
+            TemporalKind::Date,
+            millis(range.start)..millis(range.end),
+            Granularity::Day,
+        )
+    }
+
+    pub fn time(name: &str, range: Range<NaiveTime>) -> Temporal {
+        let millis = |t: NaiveTime| {
+            t.num_seconds_from_midnight() as i64 * 1_000 + t.nanosecond() as i64 / 1_000_000
+        };
+        Temporal::new(
+            name,
+            TemporalKind::Time,
+            millis(range.start)..millis(range.end),
+            Granularity::Second,
+        )
+    }
+
+    pub fn timestamp<Z: chrono::TimeZone>(name: &str, range: Range<DateTime<Z>>) -> Temporal {
+        Temporal::new(
+            name,
+            TemporalKind::Timestamp,
+            range.start.timestamp_millis()..range.end.timestamp_millis(),
+            Granularity::Second,
+        )
+    }
+
+    pub fn interval(name: &str, range: Range<TimeDelta>) -> Temporal {
+        Temporal::new(
+            name,
+            TemporalKind::Interval,
+            range.start.num_milliseconds()..range.end.num_milliseconds(),
+            Granularity::Second,
+        )
+    }
+
+    pub fn granularity(mut self, granularity: Granularity) -> Temporal {
+        self.granularity = granularity;
+        self
+    }
+
+    pub fn time_zone(mut self, time_zone: Tz) -> Temporal {
+        self.time_zone = time_zone;
+        self
+    }
+
+    pub fn format(mut self, format: TemporalFormat) -> Temporal {
+        self.format = format;
+        self
+    }
+
+    pub fn nas(mut self, nas: Nas) -> Temporal {
+        self.nas = nas;
+        self
+    }
+
+    pub(crate) fn inclusive(mut self) -> Temporal {
+        self.range.end = self.range.end.saturating_add(self.step());
+        self
+    }
+
+    pub fn validate(&self) -> io::Result<()> {
+        if self.range.start >= self.range.end {
+            let msg = format!("range of {} is empty", self.name);
+            return Err(io::Error::new(io::ErrorKind::InvalidInput, msg));
+        }
+        if self.range.end.checked_sub(self.range.start).is_none() {
+            let msg = format!("range of {} is too wide", self.name);
+            return Err(io::Error::new(io::ErrorKind::InvalidInput, msg));
+        }
+        if let TemporalFormat::Pattern(pattern) = &self.format {
+            if self.kind == TemporalKind::Interval {
+                let msg = format!(
+                    "interval column {} does not support format patterns",
+                    self.name
+                );
+                return Err(io::Error::new(io::ErrorKind::InvalidInput, msg));
+            }
+            let mut s = String::new();
+            let valid = StrftimeItems::new(pattern).all(|item| item != Item::Error)
+                && match self.kind {
+                    TemporalKind::Timestamp => {
+                        write!(s, "{}", self.zoned(self.range.start).format(pattern)).is_ok()
+                    }
+                    _ => write!(s, "{}", self.naive(self.range.start).format(pattern)).is_ok(),
+                };
+            if !valid {
+                let msg = format!("invalid format pattern of {}: {}", self.name, pattern);
+                return Err(io::Error::new(io::ErrorKind::InvalidInput, msg));
+            }
+        }
+        Ok(())
+    }
+
+    fn step(&self) -> i64 {
+        match self.kind {
+            TemporalKind::Date => self.granularity.millis().max(MILLIS_PER_DAY),
+            _ => self.granularity.millis(),
+        }
+    }
+
+    fn domain(&self) -> Range<u64> {
+        let span = self.range.end.saturating_sub(self.range.start).max(1);
+        0..((span - 1) / self.step() + 1) as u64
+    }
+
+    fn render(&self, millis: i64) -> String {
+        match (&self.format, self.kind) {
+            (TemporalFormat::EpochSeconds, _) => millis.div_euclid(1_000).to_string(),
+            (TemporalFormat::EpochMillis, _) => millis.to_string(),
+            (_, TemporalKind::Interval) => iso8601_duration(millis),
+            (TemporalFormat::Iso8601, TemporalKind::Date) => {
+                self.naive(millis).format("%Y-%m-%d").to_string()
+            }
+            (TemporalFormat::Iso8601, TemporalKind::Time) if self.step() % 1_000 == 0 => {
+                self.naive(millis).format("%H:%M:%S").to_string()
+            }
+            (TemporalFormat::Iso8601, TemporalKind::Time) => {
+                self.naive(millis).format("%H:%M:%S%.3f").to_string()
+            }
+            (TemporalFormat::Iso8601, TemporalKind::Timestamp) => {
+                let seconds = if self.step() % 1_000 == 0 {
+                    SecondsFormat::Secs
+                } else {
+                    SecondsFormat::Millis
+                };
+                self.zoned(millis)
+                    .to_rfc3339_opts(seconds, self.time_zone == Tz::UTC)
+            }
+            (TemporalFormat::Pattern(pattern), TemporalKind::Timestamp) => {
+                self.zoned(millis).format(pattern).to_string()
+            }
+            (TemporalFormat::Pattern(pattern), _) => self.naive(millis).format(pattern).to_string(),
+        }
+    }
+
+    fn naive(&self, millis: i64) -> chrono::NaiveDateTime {
+        DateTime::from_timestamp_millis(millis)
+            .unwrap_or_default()
+            .naive_utc()
+    }
+
+    fn zoned(&self, millis: i64) -> DateTime<Tz> {
+        DateTime::from_timestamp_millis(millis)
+            .unwrap_or_default()
+            .with_timezone(&self.time_zone)
+    }
+}
+
+fn iso8601_duration(millis: i64) -> String {
+    let mut s = String::from(if millis < 0 { "-P" } else { "P" });
+    let millis = millis.unsigned_abs();
+    let (days, rest) = (
+        millis / MILLIS_PER_DAY as u64,
+        millis % MILLIS_PER_DAY as u64,
+    );
+    if days > 0 {
+        let _ = write!(s, "{}D", days);
+    }
+    if rest > 0 || days == 0 {
+        s.push('T');
+        let (hours, minutes) = (rest / 3_600_000, rest / 60_000 % 60);
+        let (seconds, fraction) = (rest / 1_000 % 60, rest % 1_000);
+        if hours > 0 {
+            let _ = write!(s, "{}H", hours);
+        }
+        if minutes > 0 {
+            let _ = write!(s, "{}M", minutes);
+        }
+        if fraction > 0 {
+            let _ = write!(s, "{}.{:03}S", seconds, fraction);
+        } else if seconds > 0 || rest == 0 {
+            let _ = write!(s, "{}S", seconds);
+        }
+    }
+    s
+}
+
+impl ColumnGenerator for Temporal {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn source(&self, seed: u64, number_of_rows: usize) -> Box<dyn CellSource + '_> {
+        Box::new(Discrete::new(
+            seed,
+            number_of_rows,
+            self.domain(),
+            Skew::Uniform,
+            self.nas,
+            |v| self.render(self.range.start + v as i64 * self.step()),
+        ))
+    }
+}
+
+impl LatentColumn for Temporal {
+    fn latent(&self, seed: u64) -> Box<dyn Latent + '_> {
+        Box::new(KeyLatent::new(seed, self.domain(), Skew::Uniform))
+    }
+}
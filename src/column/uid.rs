@@ -0,0 +1,156 @@
+use crate::column::{CellSource, Cells, ColumnGenerator, NaSet, Nas};
+use crate::utils::rand::{split as split_seed, Philox, Rand};
+use chrono::{DateTime, TimeDelta, Utc};
+use std::io;
+use std::ops::Range;
+
+const MAX_TIMESTAMP: i64 = (1 << 48) - 1;
+
+const CROCKFORD: &[u8; 32] = b"0123456789ABCDEFGHJKMNPQRSTVWXYZ";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum UidKind {
+    UuidV4,
+    UuidV7,
+    Ulid,
+}
+
+#[derive(Clone, Debug)]
+pub struct UniqueId {
+    name: String,
+    kind: UidKind,
+    start: i64,
+    interval: i64,
+    nas: Nas,
+}
+
+impl UniqueId {
+    fn new(name: &str, kind: UidKind) -> UniqueId {
+        UniqueId {
+            name: name.to_string(),
+            kind,
+            start: 1_704_067_200_000,
+            interval: 1,
+            nas: Nas::None,
+        }
+    }
+
+    pub fn uuid_v4(name: &str) -> UniqueId {
+        UniqueId::new(name, UidKind::UuidV4)
+    }
+
+    pub fn uuid_v7(name: &str) -> UniqueId {
+        UniqueId::new(name, UidKind::UuidV7)
+    }
+
+    pub fn ulid(name: &str) -> UniqueId {
+        UniqueId::new(name, UidKind::Ulid)
+    }
+
+    pub fn start(mut self, start: DateTime<Utc>) -> UniqueId {
+        self.start = start.timestamp_millis();
+        self
+    }
+
+    pub fn interval(mut self, interval: TimeDelta) -> UniqueId {
+        self.interval = interval.num_milliseconds();
+        self
+    }
+
+    pub fn nas(mut self, nas: Nas) -> UniqueId {
+        self.nas = nas;
+        self
+    }
+
+    pub fn validate(&self, number_of_rows: usize) -> io::Result<()> {
+        if self.kind == UidKind::UuidV4 {
+            return Ok(());
+        }
+        let last = i64::try_from(number_of_rows.saturating_sub(1))
+            .ok()
+            .and_then(|i| self.interval.checked_mul(i))
+            .and_then(|offset| self.start.checked_add(offset));
+        let valid = |t: i64| (0..=MAX_TIMESTAMP).contains(&t);
+        match last {
+            Some(last) if valid(self.start) && valid(last) => Ok(()),
+            _ => {
+                let msg = format!("timestamps of {} are out of range", self.name);
+                Err(io::Error::new(io::ErrorKind::InvalidInput, msg))
+            }
+        }
+    }
+
+    fn render(&self, bits: u128) -> String {
+        match self.kind {
+            UidKind::UuidV4 | UidKind::UuidV7 => format!(
+                "{:08x}-{:04x}-{:04x}-{:04x}-{:012x}",
+                bits >> 96,
+                bits >> 80 & 0xffff,
+                bits >> 64 & 0xffff,
+                bits >> 48 & 0xffff,
+                bits & 0xffff_ffff_ffff
+            ),
+            UidKind::Ulid => (0..26)
+                .map(|i| CROCKFORD[(bits >> (125 - 5 * i) & 0x1f) as usize] as char)
+                .collect(),
+        }
+    }
+}
+
+struct UniqueIdSource<'a> {
+    column: &'a UniqueId,
+    rand: Philox,
+    nas: NaSet<u128>,
+}
+
+impl UniqueIdSource<'_> {
+    fn value(&self, i: usize) -> u128 {
+        let mut rand = self.rand.row(i as u64);
+        let random = (rand.next_u64() as u128) << 64 | rand.next_u64() as u128;
+        let offset = self.column.interval.wrapping_mul(i as i64);
+        let timestamp = self.column.start.wrapping_add(offset) as u128 & 0xffff_ffff_ffff;
+        match self.column.kind {
+            UidKind::UuidV4 => random & !(0xf << 76 | 0x3 << 62) | 0x4 << 76 | 0x2 << 62,
+            UidKind::UuidV7 => {
+                timestamp << 80
+                    | 0x7 << 76
+                    | random & (0xfff << 64 | 0x3fff_ffff_ffff_ffff)
+                    | 0x2 << 62
+            }
+            UidKind::Ulid => timestamp << 80 | random & ((1 << 80) - 1),
+        }
+    }
+}
+
+impl CellSource for UniqueIdSource<'_> {
+    fn cells(&self, rows: Range<usize>) -> Cells<'_> {
+        let values = rows.clone().map(|i| self.value(i));
+        Box::new(
+            self.nas
+                .apply(rows, values)
+                .map(|v| v.map(|v| self.column.render(v))),
+        )
+    }
+}
+
+impl ColumnGenerator for UniqueId {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn source(&self, seed: u64, number_of_rows: usize) -> Box<dyn CellSource + '_> {
+        let na_seed = split_seed(seed, "nas");
+        let nas = match self.nas {
+            Nas::ByValue(ratio) | Nas::ByIndex(ratio) if ratio > 0 => {
+                NaSet::by_index(number_of_rows, ratio, na_seed)
+            }
+            Nas::WithProbability(ratio) if ratio > 0 => NaSet::with_probability(ratio, na_seed),
+            _ => NaSet::None,
+        };
+        Box::new(UniqueIdSource {
+            column: self,
+            rand: Philox::new(split_seed(seed, "values")),
+            nas,
+        })
+    }
+}
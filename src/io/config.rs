@@ -1,7 +1,222 @@
 use csv;
 use std::fs;
 use std::io;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Compression {
+    #[default]
+    None,
+    Gzip,
+    Zstd,
+    Bzip2,
+    Xz,
+    Lz4,
+}
+
+impl Compression {
+    pub fn from_path(path: &Path) -> Compression {
+        match path.extension().and_then(|v| v.to_str()) {
+            Some("gz" | "gzip") => Compression::Gzip,
+            Some("zst" | "zstd") => Compression::Zstd,
+            Some("bz2") => Compression::Bzip2,
+            Some("xz") => Compression::Xz,
+            Some("lz4") => Compression::Lz4,
+            _ => Compression::None,
+        }
+    }
+
+    #[cfg(not(all(
+        feature = "bzip2",
+        feature = "gzip",
+        feature = "lz4",
+        feature = "xz",
+        feature = "zstd"
+    )))]
+    fn unsupported(self) -> io::Error {
+        let msg = format!("{:?} compression is not enabled in this build", self);
+        io::Error::new(io::ErrorKind::Unsupported, msg)
+    }
+
+    #[cfg_attr(
+        not(any(feature = "bzip2", feature = "gzip", feature = "xz", feature = "zstd")),
+        allow(unused_variables)
+    )]
+    fn encoder(
+        self,
+        writer: Box<dyn io::Write + 'static>,
+        level: Option<u32>,
+    ) -> io::Result<Encoder> {
+        let codec = match self {
+            Compression::None => Codec::None(writer),
+            #[cfg(feature = "gzip")]
+            Compression::Gzip => Codec::Gzip(flate2::write::GzEncoder::new(
+                writer,
+                flate2::Compression::new(level.unwrap_or(6).min(9)),
+            )),
+            #[cfg(feature = "zstd")]
+            Compression::Zstd => Codec::Zstd(zstd::Encoder::new(
+                writer,
+                level.unwrap_or(3).min(22) as i32,
+            )?),
+            #[cfg(feature = "bzip2")]
+            Compression::Bzip2 => Codec::Bzip2(bzip2::write::BzEncoder::new(
+                writer,
+                bzip2::Compression::new(level.unwrap_or(6).clamp(1, 9)),
+            )),
+            #[cfg(feature = "xz")]
+            Compression::Xz => Codec::Xz(xz2::write::XzEncoder::new(
+                writer,
+                level.unwrap_or(6).min(9),
+            )),
+            #[cfg(feature = "lz4")]
+            Compression::Lz4 => Codec::Lz4(lz4_flex::frame::FrameEncoder::new(writer)),
+            #[cfg(not(all(
+                feature = "bzip2",
+                feature = "gzip",
+                feature = "lz4",
+                feature = "xz",
+                feature = "zstd"
+            )))]
+            _ => return Err(self.unsupported()),
+        };
+        Ok(Encoder {
+            codec,
+            finished: false,
+        })
+    }
+
+    fn decoder(
+        self,
+        reader: Box<dyn io::Read + 'static>,
+    ) -> io::Result<Box<dyn io::Read + 'static>> {
+        Ok(match self {
+            Compression::None => reader,
+            #[cfg(feature = "gzip")]
+            Compression::Gzip => Box::new(flate2::read::MultiGzDecoder::new(reader)),
+            #[cfg(feature = "zstd")]
+            Compression::Zstd => Box::new(zstd::Decoder::new(reader)?),
+            #[cfg(feature = "bzip2")]
+            Compression::Bzip2 => Box::new(bzip2::read::MultiBzDecoder::new(reader)),
+            #[cfg(feature = "xz")]
+            Compression::Xz => Box::new(xz2::read::XzDecoder::new_multi_decoder(reader)),
+            #[cfg(feature = "lz4")]
+            Compression::Lz4 => Box::new(lz4_flex::frame::FrameDecoder::new(reader)),
+            #[cfg(not(all(
+                feature = "bzip2",
+                feature = "gzip",
+                feature = "lz4",
+                feature = "xz",
+                feature = "zstd"
+            )))]
+            _ => return Err(self.unsupported()),
+        })
+    }
+}
+
+enum Codec {
+    None(Box<dyn io::Write + 'static>),
+    #[cfg(feature = "gzip")]
+    Gzip(flate2::write::GzEncoder<Box<dyn io::Write + 'static>>),
+    #[cfg(feature = "zstd")]
+    Zstd(zstd::Encoder<'static, Box<dyn io::Write + 'static>>),
+    #[cfg(feature = "bzip2")]
+    Bzip2(bzip2::write::BzEncoder<Box<dyn io::Write + 'static>>),
+    #[cfg(feature = "xz")]
+    Xz(xz2::write::XzEncoder<Box<dyn io::Write + 'static>>),
+    #[cfg(feature = "lz4")]
+    Lz4(lz4_flex::frame::FrameEncoder<Box<dyn io::Write + 'static>>),
+}
+
+pub struct Encoder {
+    codec: Codec,
+    finished: bool,
+}
+
+impl Encoder {
+    pub fn finish(mut self) -> io::Result<()> {
+        self.try_finish()
+    }
+
+    fn try_finish(&mut self) -> io::Result<()> {
+        if !self.finished {
+            match self.codec {
+                Codec::None(_) => {}
+                #[cfg(feature = "gzip")]
+                Codec::Gzip(ref mut w) => w.try_finish()?,
+                #[cfg(feature = "zstd")]
+                Codec::Zstd(ref mut w) => w.do_finish()?,
+                #[cfg(feature = "bzip2")]
+                Codec::Bzip2(ref mut w) => w.try_finish()?,
+                #[cfg(feature = "xz")]
+                Codec::Xz(ref mut w) => w.try_finish()?,
+                #[cfg(feature = "lz4")]
+                Codec::Lz4(ref mut w) => w.try_finish()?,
+            }
+            self.finished = true;
+        }
+        self.inner().flush()
+    }
+
+    fn inner(&mut self) -> &mut Box<dyn io::Write + 'static> {
+        match self.codec {
+            Codec::None(ref mut w) => w,
+            #[cfg(feature = "gzip")]
+            Codec::Gzip(ref mut w) => w.get_mut(),
+            #[cfg(feature = "zstd")]
+            Codec::Zstd(ref mut w) => w.get_mut(),
+            #[cfg(feature = "bzip2")]
+            Codec::Bzip2(ref mut w) => w.get_mut(),
+            #[cfg(feature = "xz")]
+            Codec::Xz(ref mut w) => w.get_mut(),
+            #[cfg(feature = "lz4")]
+            Codec::Lz4(ref mut w) => w.get_mut(),
+        }
+    }
+}
+
+impl io::Write for Encoder {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        match self.codec {
+            Codec::None(ref mut w) => w.write(buf),
+            #[cfg(feature = "gzip")]
+            Codec::Gzip(ref mut w) => w.write(buf),
+            #[cfg(feature = "zstd")]
+            Codec::Zstd(ref mut w) => w.write(buf),
+            #[cfg(feature = "bzip2")]
+            Codec::Bzip2(ref mut w) => w.write(buf),
+            #[cfg(feature = "xz")]
+            Codec::Xz(ref mut w) => w.write(buf),
+            #[cfg(feature = "lz4")]
+            Codec::Lz4(ref mut w) => w.write(buf),
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        if self.finished {
+            return self.inner().flush();
+        }
+        match self.codec {
+            Codec::None(ref mut w) => w.flush(),
+            #[cfg(feature = "gzip")]
+            Codec::Gzip(ref mut w) => w.flush(),
+            #[cfg(feature = "zstd")]
+            Codec::Zstd(ref mut w) => w.flush(),
+            #[cfg(feature = "bzip2")]
+            Codec::Bzip2(ref mut w) => w.flush(),
+            #[cfg(feature = "xz")]
+            Codec::Xz(ref mut w) => w.flush(),
+            #[cfg(feature = "lz4")]
+            Codec::Lz4(ref mut w) => w.flush(),
+        }
+    }
+}
+
+impl Drop for Encoder {
+    fn drop(&mut self) {
+        let _ = self.try_finish();
+    }
+}
 
 #[derive(Debug)]
 pub struct Config {
@@ -15,6 +230,8 @@ pub struct Config {
     double_quote: bool,
     escape: Option<u8>,
     quoting: bool,
+    compression: Compression,
+    compression_level: Option<u32>,
 }
 
 impl Default for Config {
@@ -30,27 +247,34 @@ impl Default for Config {
             double_quote: false,
             escape: None,
             quoting: true,
+            compression: Compression::None,
+            compression_level: None,
         }
     }
 }
 
 impl Config {
     pub fn new(path: &str) -> Config {
-        let (path, delimiter) = match path {
-            s if s == "-" => (None, b','),
+        let (path, delimiter, compression) = match path {
+            "-" => (None, b',', Compression::None),
             s => {
                 let path = PathBuf::from(s);
-                let delimiter = if path.extension().map_or(false, |v| v == "tsv" || v == "tab") {
+                let compression = Compression::from_path(&path);
+                let name = match compression {
+                    Compression::None => path.clone(),
+                    _ => path.with_extension(""),
+                };
+                let delimiter = if name.extension().is_some_and(|v| v == "tsv" || v == "tab") {
                     b'\t'
                 } else {
                     b','
                 };
-                (Some(path), delimiter)
+                (Some(path), delimiter, compression)
             }
         };
         Config {
-            path: path,
-            delimiter: delimiter,
+            path,
+            delimiter,
             no_headers: true,
             flexible: true,
             terminator: csv::Terminator::Any(b'\n'),
@@ -59,6 +283,8 @@ impl Config {
             double_quote: false,
             escape: None,
             quoting: true,
+            compression,
+            compression_level: None,
         }
     }
 
@@ -116,6 +342,16 @@ impl Config {
         self
     }
 
+    pub fn compression(mut self, compression: Compression) -> Config {
+        self.compression = compression;
+        self
+    }
+
+    pub fn compression_level(mut self, level: u32) -> Config {
+        self.compression_level = Some(level);
+        self
+    }
+
     pub fn is_stdout(&self) -> bool {
         self.path.is_none()
     }
@@ -138,12 +374,12 @@ impl Config {
         Ok(self.from_reader(self.io_reader()?))
     }
 
-    pub fn writer(&self) -> io::Result<csv::Writer<Box<dyn io::Write + 'static>>> {
+    pub fn writer(&self) -> io::Result<csv::Writer<Encoder>> {
         Ok(self.from_writer(self.io_writer()?))
     }
 
     pub fn io_reader(&self) -> io::Result<Box<dyn io::Read + 'static>> {
-        Ok(match self.path {
+        let reader: Box<dyn io::Read + 'static> = match self.path {
             None => Box::new(io::stdin()),
             Some(ref p) => match fs::File::open(p) {
                 Ok(x) => Box::new(x),
@@ -152,14 +388,16 @@ impl Config {
                     return Err(io::Error::new(io::ErrorKind::NotFound, msg));
                 }
             },
-        })
+        };
+        self.compression.decoder(reader)
     }
 
-    pub fn io_writer(&self) -> io::Result<Box<dyn io::Write + 'static>> {
-        Ok(match self.path {
+    pub fn io_writer(&self) -> io::Result<Encoder> {
+        let writer: Box<dyn io::Write + 'static> = match self.path {
             None => Box::new(io::stdout()),
             Some(ref p) => Box::new(fs::File::create(p)?),
-        })
+        };
+        self.compression.encoder(writer, self.compression_level)
     }
 
     pub fn from_reader<R: io::Read>(&self, reader: R) -> csv::Reader<R> {
@@ -188,10 +426,10 @@ impl Config {
 
     pub fn reader_file(&self) -> io::Result<csv::Reader<fs::File>> {
         match self.path {
-            None => Err(io::Error::new(
-                io::ErrorKind::Other,
-                "cannot use <stdin> here",
-            )),
+            None => Err(io::Error::other("cannot use <stdin> here")),
+            Some(_) if self.compression != Compression::None => {
+                Err(io::Error::other("cannot use compressed input here"))
+            }
             Some(ref p) => fs::File::open(p).map(|f| self.from_reader(f)),
         }
     }
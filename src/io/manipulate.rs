@@ -54,13 +54,23 @@ pub fn hstack<R: io::Read, W: io::Write>(
         let row = rows
             .iter()
             .filter_map(|f| f.as_ref().ok())
-            .map(csv::ByteRecord::iter)
-            .flatten();
+            .flat_map(csv::ByteRecord::iter);
         writer.write_record(row)?;
     }
     Ok(())
 }
 
+pub fn dump<W, I>(writer: &mut csv::Writer<W>, cells: I) -> csv::Result<()>
+where
+    W: io::Write,
+    I: Iterator<Item = Option<String>>,
+{
+    for cell in cells {
+        writer.write_record([cell.as_deref().unwrap_or("")])?;
+    }
+    Ok(())
+}
+
 fn lex_ordering<V, L, R>(mut a: L, mut b: R) -> cmp::Ordering
 where
     V: Ord,
@@ -80,10 +90,33 @@ where
     }
 }
 
+fn key_ordering<'a, L, R>(keys: &[usize], a: L, b: R) -> cmp::Ordering
+where
+    L: Iterator<Item = &'a [u8]> + Clone,
+    R: Iterator<Item = &'a [u8]> + Clone,
+{
+    if keys.is_empty() {
+        return lex_ordering(a, b);
+    }
+    lex_ordering(
+        keys.iter().map(|&k| a.clone().nth(k)),
+        keys.iter().map(|&k| b.clone().nth(k)),
+    )
+}
+
 pub fn sort_chunk<R: io::Read, W: io::Write>(
     number_of_rows: Option<usize>,
     writer: &mut csv::Writer<W>,
     zipped_iter: &mut Zip<csv::ByteRecordsIter<'_, R>>,
+) -> csv::Result<()> {
+    sort_chunk_by(&[], number_of_rows, writer, zipped_iter)
+}
+
+pub fn sort_chunk_by<R: io::Read, W: io::Write>(
+    keys: &[usize],
+    number_of_rows: Option<usize>,
+    writer: &mut csv::Writer<W>,
+    zipped_iter: &mut Zip<csv::ByteRecordsIter<'_, R>>,
 ) -> csv::Result<()> {
     let mut zipped_byte_records: Vec<_> = match number_of_rows {
         Some(number_of_rows) => zipped_iter.take(number_of_rows).collect(),
@@ -93,41 +126,47 @@ pub fn sort_chunk<R: io::Read, W: io::Write>(
         let lhs = lhs
             .iter()
             .filter_map(|f| f.as_ref().ok())
-            .map(csv::ByteRecord::iter)
-            .flatten();
+            .flat_map(csv::ByteRecord::iter);
         let rhs = rhs
             .iter()
             .filter_map(|f| f.as_ref().ok())
-            .map(csv::ByteRecord::iter)
-            .flatten();
-        lex_ordering(lhs, rhs)
+            .flat_map(csv::ByteRecord::iter);
+        key_ordering(keys, lhs, rhs)
     });
     for rows in zipped_byte_records {
         let row = rows
             .iter()
             .filter_map(|f| f.as_ref().ok())
-            .map(csv::ByteRecord::iter)
-            .flatten();
+            .flat_map(csv::ByteRecord::iter);
         writer.write_record(row)?;
     }
     Ok(())
 }
 
+#[allow(clippy::ptr_arg)]
 pub fn merge_sort<R: io::Read, W: io::Write>(
     writer: &mut csv::Writer<W>,
     readers: &mut Vec<csv::Reader<R>>,
 ) -> csv::Result<()> {
-    let mut readers: Vec<_> = readers.into_iter().map(csv::Reader::byte_records).collect();
+    merge_sort_by(&[], writer, readers)
+}
+
+pub fn merge_sort_by<R: io::Read, W: io::Write>(
+    keys: &[usize],
+    writer: &mut csv::Writer<W>,
+    readers: &mut [csv::Reader<R>],
+) -> csv::Result<()> {
+    let mut readers: Vec<_> = readers.iter_mut().map(csv::Reader::byte_records).collect();
     let mut values: Vec<_> = readers
         .iter_mut()
         .filter_map(|r| r.next())
         .filter_map(|r| r.ok())
         .collect();
-    while values.len() > 0 {
+    while !values.is_empty() {
         let (i, next) = values
             .iter()
             .enumerate()
-            .min_by(|(_, lhs), (_, rhs)| lex_ordering(lhs.iter(), rhs.iter()))
+            .min_by(|(_, lhs), (_, rhs)| key_ordering(keys, lhs.iter(), rhs.iter()))
             .unwrap();
         writer.write_record(next)?;
         if let Some(x) = readers[i].next() {
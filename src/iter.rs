@@ -1,6 +1,9 @@
+pub mod approx;
 pub mod extensions;
 mod none_by;
+mod sample;
 mod unique_by;
+mod unique_on_disk;
 
 pub type KeySet<V> = hashbrown::HashSet<V, ahash::RandomState>;
 
@@ -0,0 +1,232 @@
+use std::hash::Hash;
+
+const HASH_SEEDS: [u64; 4] = [
+    0x243f6a8885a308d3,
+    0x13198a2e03707344,
+    0xa4093822299f31d0,
+    0x082efa98ec4e6c89,
+];
+
+fn hasher() -> ahash::RandomState {
+    ahash::RandomState::with_seeds(HASH_SEEDS[0], HASH_SEEDS[1], HASH_SEEDS[2], HASH_SEEDS[3])
+}
+
+#[derive(Clone, Debug)]
+pub struct HyperLogLog {
+    precision: u32,
+    registers: Vec<u8>,
+    hasher: ahash::RandomState,
+}
+
+impl HyperLogLog {
+    pub const MIN_PRECISION: u32 = 4;
+    pub const MAX_PRECISION: u32 = 18;
+
+    pub fn new(error: f64) -> HyperLogLog {
+        let precision = if error.is_finite() && error > 0.0 {
+            (1.04 / error)
+                .powi(2)
+                .max(1.0)
+                .log2()
+                .ceil()
+                .min(u32::MAX as f64) as u32
+        } else {
+            HyperLogLog::MAX_PRECISION
+        };
+        HyperLogLog::with_precision(precision)
+    }
+
+    pub fn with_precision(precision: u32) -> HyperLogLog {
+        let precision = precision.clamp(HyperLogLog::MIN_PRECISION, HyperLogLog::MAX_PRECISION);
+        HyperLogLog {
+            precision,
+            registers: vec![0; 1 << precision],
+            hasher: hasher(),
+        }
+    }
+
+    pub fn error(&self) -> f64 {
+        1.04 / (self.registers.len() as f64).sqrt()
+    }
+
+    pub fn insert<V: Hash + ?Sized>(&mut self, value: &V) {
+        let hash = self.hasher.hash_one(value);
+        let index = (hash >> (64 - self.precision)) as usize;
+        let rank = ((hash << self.precision) | 1 << (self.precision - 1)).leading_zeros() + 1;
+        let register = &mut self.registers[index];
+        *register = (*register).max(rank as u8);
+    }
+
+    pub fn merge(&mut self, other: &HyperLogLog) {
+        assert_eq!(
+            self.precision, other.precision,
+            "cannot merge HyperLogLog sketches of different precision"
+        );
+        for (r, o) in self.registers.iter_mut().zip(other.registers.iter()) {
+            *r = (*r).max(*o);
+        }
+    }
+
+    pub fn estimate(&self) -> f64 {
+        let m = self.registers.len() as f64;
+        let alpha = match self.registers.len() {
+            16 => 0.673,
+            32 => 0.697,
+            64 => 0.709,
+            _ => 0.7213 / (1.0 + 1.079 / m),
+        };
+        let sum: f64 = self.registers.iter().map(|&r| (-(r as f64)).exp2()).sum();
+        let raw = alpha * m * m / sum;
+        let zeros = self.registers.iter().filter(|&&r| r == 0).count();
+        if raw <= 2.5 * m && zeros > 0 {
+            m * (m / zeros as f64).ln()
+        } else {
+            raw
+        }
+    }
+}
+
+impl<V: Hash> Extend<V> for HyperLogLog {
+    fn extend<T: IntoIterator<Item = V>>(&mut self, iter: T) {
+        for v in iter {
+            self.insert(&v);
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct BloomFilter {
+    bits: Vec<u64>,
+    size: u64,
+    hashes: u32,
+    hasher: ahash::RandomState,
+}
+
+impl BloomFilter {
+    pub fn new(expected: usize, false_positive_rate: f64) -> BloomFilter {
+        let expected = expected.max(1) as f64;
+        let rate = false_positive_rate.clamp(f64::MIN_POSITIVE, 0.5);
+        let ln2 = std::f64::consts::LN_2;
+        let size = (-expected * rate.ln() / (ln2 * ln2)).ceil().max(64.0) as u64;
+        let hashes = (size as f64 / expected * ln2).round().clamp(1.0, 32.0) as u32;
+        BloomFilter {
+            bits: vec![0; size.div_ceil(64) as usize],
+            size,
+            hashes,
+            hasher: hasher(),
+        }
+    }
+
+    fn positions<V: Hash + ?Sized>(&self, value: &V) -> impl Iterator<Item = (usize, u64)> {
+        let h1 = self.hasher.hash_one(value);
+        let h2 = (h1 ^ h1 >> 33).wrapping_mul(0xff51afd7ed558ccd) | 1;
+        let size = self.size;
+        (0..self.hashes as u64).map(move |i| {
+            let p = h1.wrapping_add(i.wrapping_mul(h2)) % size;
+            ((p / 64) as usize, 1 << (p % 64))
+        })
+    }
+
+    pub fn contains<V: Hash + ?Sized>(&self, value: &V) -> bool {
+        self.positions(value)
+            .all(|(word, mask)| self.bits[word] & mask != 0)
+    }
+
+    pub fn insert<V: Hash + ?Sized>(&mut self, value: &V) -> bool {
+        let mut inserted = false;
+        for (word, mask) in self.positions(value) {
+            inserted |= self.bits[word] & mask == 0;
+            self.bits[word] |= mask;
+        }
+        inserted
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hyperloglog_precision_is_clamped() {
+        assert_eq!(HyperLogLog::new(1.0).error(), 1.04 / 4.0);
+        assert_eq!(HyperLogLog::new(1e9).error(), 1.04 / 4.0);
+        assert_eq!(
+            HyperLogLog::new(f64::INFINITY).error(),
+            HyperLogLog::new(0.0).error()
+        );
+        assert_eq!(
+            HyperLogLog::with_precision(0).error(),
+            HyperLogLog::new(1.0).error()
+        );
+        assert_eq!(
+            HyperLogLog::with_precision(64).error(),
+            HyperLogLog::with_precision(HyperLogLog::MAX_PRECISION).error()
+        );
+        assert!(HyperLogLog::new(0.01).error() <= 0.01);
+    }
+
+    #[test]
+    fn hyperloglog_estimate_is_within_error() {
+        for (error, n) in [
+            (0.05, 0),
+            (0.05, 10),
+            (0.02, 1000),
+            (0.02, 100_000),
+            (0.1, 1_000_000),
+        ] {
+            let mut hll = HyperLogLog::new(error);
+            hll.extend(0..n);
+            hll.extend(0..n);
+            let estimate = hll.estimate();
+            let bound = 4.0 * hll.error() * n as f64 + 1.0;
+            assert!(
+                (estimate - n as f64).abs() <= bound,
+                "{} of {}",
+                estimate,
+                n
+            );
+        }
+    }
+
+    #[test]
+    fn hyperloglog_merge_matches_union() {
+        let (mut a, mut b, mut union) = (
+            HyperLogLog::new(0.02),
+            HyperLogLog::new(0.02),
+            HyperLogLog::new(0.02),
+        );
+        a.extend(0..60_000);
+        b.extend(40_000..100_000);
+        union.extend(0..100_000);
+        a.merge(&b);
+        assert_eq!(a.estimate(), union.estimate());
+    }
+
+    #[test]
+    fn bloom_filter_has_no_false_negatives() {
+        let mut filter = BloomFilter::new(10_000, 0.01);
+        assert!((0..10_000).all(|i| filter.insert(&i) || filter.contains(&i)));
+        assert!((0..10_000).all(|i| filter.contains(&i)));
+        assert!((0..10_000).all(|i| !filter.insert(&i)));
+    }
+
+    #[test]
+    fn bloom_filter_false_positive_rate_is_bounded() {
+        for (expected, rate) in [(1, 0.5), (1000, 0.1), (10_000, 0.01), (100_000, 0.001)] {
+            let mut filter = BloomFilter::new(expected, rate);
+            (0..expected as u64).for_each(|i| {
+                filter.insert(&i);
+            });
+            let trials = 100_000;
+            let positives = (expected as u64..expected as u64 + trials)
+                .filter(|i| filter.contains(i))
+                .count();
+            assert!(
+                positives as f64 <= 2.0 * rate * trials as f64,
+                "{} at {}",
+                positives,
+                rate
+            );
+        }
+    }
+}
@@ -1,8 +1,26 @@
-use crate::iter::none_by::{none_by_index, none_by_value, NoneByIndex, NoneByValue};
-use crate::iter::unique_by::{unique, unique_by, Unique, UniqueBy};
+use crate::iter::approx::HyperLogLog;
+use crate::iter::none_by::{
+    none_by_index, none_by_sample, none_by_value, none_with_probability, NoneByIndex, NoneBySample,
+    NoneByValue, NoneWithProbability,
+};
+pub use crate::iter::sample::choose_range_seeded;
+use crate::iter::sample::{reservoir, weighted_reservoir};
+use crate::iter::unique_by::{
+    approx_unique, approx_unique_by, unique, unique_by, ApproxUnique, ApproxUniqueBy, Unique,
+    UniqueBy,
+};
+use crate::iter::unique_on_disk::{unique_on_disk, UniqueOnDisk};
 pub use crate::iter::KeySet;
-use crate::utils::rand::{init as init_rand, RandRange};
+use crate::utils::rand::init as init_rand;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
 use std::hash::Hash;
+use std::ops::Range;
+
+pub fn choose_range(domain: Range<u64>, amount: usize) -> KeySet<u64> {
+    let (seed, _) = init_rand();
+    choose_range_seeded(domain, amount, seed)
+}
 
 pub trait UniqueValueIterator: Iterator {
     fn unique(self) -> Unique<Self>
@@ -21,33 +39,187 @@ pub trait UniqueValueIterator: Iterator {
     {
         unique_by(self, f)
     }
+
+    fn approx_unique(self, expected: usize, false_positive_rate: f64) -> ApproxUnique<Self>
+    where
+        Self: Sized,
+        Self::Item: Hash,
+    {
+        approx_unique(self, expected, false_positive_rate)
+    }
+
+    fn approx_unique_by<V, F>(
+        self,
+        expected: usize,
+        false_positive_rate: f64,
+        f: F,
+    ) -> ApproxUniqueBy<Self, F>
+    where
+        Self: Sized,
+        V: Hash,
+        F: FnMut(&Self::Item) -> V,
+    {
+        approx_unique_by(self, expected, false_positive_rate, f)
+    }
+
+    fn unique_on_disk(self) -> UniqueOnDisk<Self>
+    where
+        Self: Sized,
+        Self::Item: Clone + Eq + Hash + Serialize + DeserializeOwned,
+    {
+        unique_on_disk(self, None)
+    }
+
+    fn unique_on_disk_with_budget(self, budget: u64) -> UniqueOnDisk<Self>
+    where
+        Self: Sized,
+        Self::Item: Clone + Eq + Hash + Serialize + DeserializeOwned,
+    {
+        unique_on_disk(self, Some(budget))
+    }
+
+    fn approx_unique_count(self, error: f64) -> usize
+    where
+        Self: Sized,
+        Self::Item: Hash,
+    {
+        let mut sketch = HyperLogLog::new(error);
+        sketch.extend(self);
+        sketch.estimate().round() as usize
+    }
+
+    fn approx_unique_count_by<V, F>(self, error: f64, mut f: F) -> usize
+    where
+        Self: Sized,
+        V: Hash,
+        F: FnMut(&Self::Item) -> V,
+    {
+        let mut sketch = HyperLogLog::new(error);
+        sketch.extend(self.map(move |e| f(&e)));
+        sketch.estimate().round() as usize
+    }
 }
 
 impl<T: ?Sized> UniqueValueIterator for T where T: Iterator {}
 
 pub trait SamplingIterator: Iterator {
-    fn choose(mut self, amount: usize) -> KeySet<Self::Item>
+    fn choose(self, amount: usize) -> KeySet<Self::Item>
     where
         Self: Sized,
         Self::Item: Clone + Eq + Hash,
     {
-        if amount < 1 {
-            return KeySet::with_capacity_and_hasher(0, Default::default());
-        }
-        let (_, mut rand) = init_rand();
-        let mut reservoir = Vec::with_capacity(amount);
-        reservoir.extend(self.by_ref().take(amount));
-        if reservoir.len() == amount {
-            for (i, e) in self.enumerate() {
-                let k = rand.next_range(0..(i + 1 + amount));
-                if let Some(s) = reservoir.get_mut(k) {
-                    *s = e;
-                }
-            }
-        } else {
-            reservoir.shrink_to_fit();
-        }
-        KeySet::from_iter(reservoir)
+        let (seed, _) = init_rand();
+        self.choose_seeded(amount, seed)
+    }
+
+    fn choose_seeded(self, amount: usize, seed: u64) -> KeySet<Self::Item>
+    where
+        Self: Sized,
+        Self::Item: Clone + Eq + Hash,
+    {
+        KeySet::from_iter(reservoir(self, amount, seed).into_iter().map(|(_, e)| e))
+    }
+
+    fn sample(self, amount: usize) -> Vec<Self::Item>
+    where
+        Self: Sized,
+    {
+        let (seed, _) = init_rand();
+        self.sample_seeded(amount, seed)
+    }
+
+    fn sample_seeded(self, amount: usize, seed: u64) -> Vec<Self::Item>
+    where
+        Self: Sized,
+    {
+        reservoir(self, amount, seed)
+            .into_iter()
+            .map(|(_, e)| e)
+            .collect()
+    }
+
+    fn sample_indexed(self, amount: usize) -> Vec<(usize, Self::Item)>
+    where
+        Self: Sized,
+    {
+        let (seed, _) = init_rand();
+        self.sample_indexed_seeded(amount, seed)
+    }
+
+    fn sample_indexed_seeded(self, amount: usize, seed: u64) -> Vec<(usize, Self::Item)>
+    where
+        Self: Sized,
+    {
+        let mut sample = reservoir(self, amount, seed);
+        sample.sort_unstable_by_key(|&(i, _)| i);
+        sample
+    }
+
+    fn choose_weighted<F>(self, amount: usize, weight: F) -> KeySet<Self::Item>
+    where
+        Self: Sized,
+        Self::Item: Clone + Eq + Hash,
+        F: FnMut(&Self::Item) -> f64,
+    {
+        let (seed, _) = init_rand();
+        self.choose_weighted_seeded(amount, seed, weight)
+    }
+
+    fn choose_weighted_seeded<F>(self, amount: usize, seed: u64, weight: F) -> KeySet<Self::Item>
+    where
+        Self: Sized,
+        Self::Item: Clone + Eq + Hash,
+        F: FnMut(&Self::Item) -> f64,
+    {
+        KeySet::from_iter(
+            weighted_reservoir(self, amount, seed, weight)
+                .into_iter()
+                .map(|(_, e)| e),
+        )
+    }
+
+    fn sample_weighted<F>(self, amount: usize, weight: F) -> Vec<Self::Item>
+    where
+        Self: Sized,
+        F: FnMut(&Self::Item) -> f64,
+    {
+        let (seed, _) = init_rand();
+        self.sample_weighted_seeded(amount, seed, weight)
+    }
+
+    fn sample_weighted_seeded<F>(self, amount: usize, seed: u64, weight: F) -> Vec<Self::Item>
+    where
+        Self: Sized,
+        F: FnMut(&Self::Item) -> f64,
+    {
+        weighted_reservoir(self, amount, seed, weight)
+            .into_iter()
+            .map(|(_, e)| e)
+            .collect()
+    }
+
+    fn sample_weighted_indexed<F>(self, amount: usize, weight: F) -> Vec<(usize, Self::Item)>
+    where
+        Self: Sized,
+        F: FnMut(&Self::Item) -> f64,
+    {
+        let (seed, _) = init_rand();
+        self.sample_weighted_indexed_seeded(amount, seed, weight)
+    }
+
+    fn sample_weighted_indexed_seeded<F>(
+        self,
+        amount: usize,
+        seed: u64,
+        weight: F,
+    ) -> Vec<(usize, Self::Item)>
+    where
+        Self: Sized,
+        F: FnMut(&Self::Item) -> f64,
+    {
+        let mut sample = weighted_reservoir(self, amount, seed, weight);
+        sample.sort_unstable_by_key(|&(i, _)| i);
+        sample
     }
 }
 
@@ -58,7 +230,53 @@ pub trait OptionalIterator: Iterator {
     where
         Self: Sized,
     {
-        none_by_index(self, indices)
+        none_by_index(self, indices, 0)
+    }
+
+    fn none_by_index_from(self, indices: KeySet<usize>, start: usize) -> NoneByIndex<Self>
+    where
+        Self: Sized,
+    {
+        none_by_index(self, indices, start)
+    }
+
+    fn none_by_sample(self, amount: usize, total: usize, seed: u64) -> NoneBySample<Self>
+    where
+        Self: Sized,
+    {
+        none_by_sample(self, amount, total, seed, 0)
+    }
+
+    fn none_by_sample_from(
+        self,
+        amount: usize,
+        total: usize,
+        seed: u64,
+        start: usize,
+    ) -> NoneBySample<Self>
+    where
+        Self: Sized,
+    {
+        none_by_sample(self, amount, total, seed, start)
+    }
+
+    fn none_with_probability(self, probability: f64, seed: u64) -> NoneWithProbability<Self>
+    where
+        Self: Sized,
+    {
+        none_with_probability(self, probability, seed, 0)
+    }
+
+    fn none_with_probability_from(
+        self,
+        probability: f64,
+        seed: u64,
+        start: usize,
+    ) -> NoneWithProbability<Self>
+    where
+        Self: Sized,
+    {
+        none_with_probability(self, probability, seed, start)
     }
 
     fn none_by_value(self, values: KeySet<Self::Item>) -> NoneByValue<Self>
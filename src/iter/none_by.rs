@@ -1,9 +1,13 @@
 use crate::debug_fmt_fields;
 use crate::iter::KeySet;
+use crate::utils::rand::distribution::hypergeometric;
+use crate::utils::rand::{rewind as rewind_rand, split_bytes, Philox, Rand, StdRand};
 use std::fmt;
 use std::hash::Hash;
 use std::iter::FusedIterator;
 
+const SAMPLE_BLOCK: usize = 1 << 12;
+
 #[derive(Clone)]
 pub struct NoneByValue<I: Iterator> {
     iter: I,
@@ -22,10 +26,7 @@ pub fn none_by_value<I>(iter: I, values: KeySet<I::Item>) -> NoneByValue<I>
 where
     I: Iterator,
 {
-    NoneByValue {
-        iter,
-        values: values,
-    }
+    NoneByValue { iter, values }
 }
 
 impl<I> Iterator for NoneByValue<I>
@@ -36,14 +37,13 @@ where
     type Item = Option<I::Item>;
 
     fn next(&mut self) -> Option<Self::Item> {
-        while let Some(v) = self.iter.next() {
+        self.iter.next().map(|v| {
             if self.values.contains(&v) {
-                return Some(None);
+                None
             } else {
-                return Some(Some(v));
+                Some(v)
             }
-        }
-        None
+        })
     }
 
     #[inline]
@@ -62,14 +62,13 @@ where
     I::Item: Hash + Eq,
 {
     fn next_back(&mut self) -> Option<Option<I::Item>> {
-        while let Some(v) = self.iter.next_back() {
+        self.iter.next_back().map(|v| {
             if self.values.contains(&v) {
-                return Some(None);
+                None
             } else {
-                return Some(Some(v));
+                Some(v)
             }
-        }
-        None
+        })
     }
 }
 
@@ -95,14 +94,14 @@ where
     debug_fmt_fields!(NoneByIndex, iter, head, indices);
 }
 
-pub fn none_by_index<I>(iter: I, indices: KeySet<usize>) -> NoneByIndex<I>
+pub fn none_by_index<I>(iter: I, indices: KeySet<usize>, head: usize) -> NoneByIndex<I>
 where
     I: Iterator,
 {
     NoneByIndex {
         iter,
-        head: 0,
-        indices: indices,
+        head,
+        indices,
     }
 }
 
@@ -114,16 +113,14 @@ where
     type Item = Option<I::Item>;
 
     fn next(&mut self) -> Option<Self::Item> {
-        while let Some(v) = self.iter.next() {
-            if self.indices.contains(&self.head) {
-                self.head += 1;
-                return Some(None);
-            } else {
-                self.head += 1;
-                return Some(Some(v));
-            }
+        let v = self.iter.next()?;
+        let head = self.head;
+        self.head += 1;
+        if self.indices.contains(&head) {
+            Some(None)
+        } else {
+            Some(Some(v))
         }
-        None
     }
 
     #[inline]
@@ -142,3 +139,309 @@ where
     I::Item: Hash + Eq,
 {
 }
+
+pub struct NoneBySample<I: Iterator> {
+    iter: I,
+    head: usize,
+    next: usize,
+    cursor: usize,
+    end: usize,
+    remaining: usize,
+    needed: usize,
+    seed: u64,
+    pending: Vec<(usize, usize, usize)>,
+    rand: StdRand,
+}
+
+impl<I> fmt::Debug for NoneBySample<I>
+where
+    I: Iterator + fmt::Debug,
+{
+    debug_fmt_fields!(NoneBySample, iter, head, next, end, remaining, needed);
+}
+
+pub fn none_by_sample<I>(
+    iter: I,
+    amount: usize,
+    total: usize,
+    seed: u64,
+    head: usize,
+) -> NoneBySample<I>
+where
+    I: Iterator,
+{
+    let mut sample = NoneBySample {
+        iter,
+        head,
+        next: usize::MAX,
+        cursor: 0,
+        end: 0,
+        remaining: 0,
+        needed: 0,
+        seed,
+        pending: vec![],
+        rand: rewind_rand(seed),
+    };
+    sample.descend(0, total, amount.min(total), head);
+    while sample.next < head {
+        sample.schedule();
+    }
+    if head >= sample.end {
+        sample.end = usize::MAX;
+    }
+    sample
+}
+
+impl<I> NoneBySample<I>
+where
+    I: Iterator,
+{
+    fn descend(&mut self, mut lo: usize, mut hi: usize, mut needed: usize, target: usize) {
+        while hi - lo > SAMPLE_BLOCK {
+            let mid = lo + (hi - lo) / 2;
+            let node = [(lo as u64).to_le_bytes(), (hi as u64).to_le_bytes()].concat();
+            let mut rand = rewind_rand(split_bytes(self.seed, &node));
+            let left = hypergeometric(
+                (hi - lo) as u64,
+                needed as u64,
+                (mid - lo) as u64,
+                &mut rand,
+            ) as usize;
+            if target < mid {
+                self.pending.push((mid, hi, needed - left));
+                hi = mid;
+                needed = left;
+            } else {
+                lo = mid;
+                needed -= left;
+            }
+        }
+        self.cursor = lo;
+        self.end = hi;
+        self.remaining = hi - lo;
+        self.needed = needed;
+        self.rand = rewind_rand(split_bytes(self.seed, &(lo as u64).to_le_bytes()));
+        self.schedule();
+    }
+
+    fn advance(&mut self) {
+        match self.pending.pop() {
+            Some((lo, hi, needed)) => self.descend(lo, hi, needed, lo),
+            None => {
+                self.next = usize::MAX;
+                self.end = usize::MAX;
+            }
+        }
+    }
+
+    fn uniform(&mut self) -> f64 {
+        (self.rand.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    fn skip(&mut self) -> usize {
+        if self.needed == 1 {
+            return ((self.remaining as f64 * self.uniform()) as usize).min(self.remaining - 1);
+        }
+        let v = self.uniform();
+        let (mut skip, mut top, mut population) = (
+            0,
+            (self.remaining - self.needed) as f64,
+            self.remaining as f64,
+        );
+        let mut quot = top / population;
+        while quot > v {
+            skip += 1;
+            top -= 1.0;
+            population -= 1.0;
+            quot *= top / population;
+        }
+        skip
+    }
+
+    fn schedule(&mut self) {
+        if self.needed == 0 {
+            self.next = usize::MAX;
+            return;
+        }
+        let skip = self.skip();
+        self.next = self.cursor + skip;
+        self.cursor = self.next + 1;
+        self.remaining -= skip + 1;
+        self.needed -= 1;
+    }
+}
+
+impl<I> Iterator for NoneBySample<I>
+where
+    I: Iterator,
+{
+    type Item = Option<I::Item>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let v = self.iter.next()?;
+        let head = self.head;
+        self.head += 1;
+        if head == self.end {
+            self.advance();
+        }
+        if head == self.next {
+            self.schedule();
+            Some(None)
+        } else {
+            Some(Some(v))
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+
+    fn count(self) -> usize {
+        self.iter.count()
+    }
+}
+
+impl<I> FusedIterator for NoneBySample<I> where I: FusedIterator {}
+
+pub struct NoneWithProbability<I: Iterator> {
+    iter: I,
+    head: usize,
+    probability: f64,
+    rand: Philox,
+}
+
+impl<I> fmt::Debug for NoneWithProbability<I>
+where
+    I: Iterator + fmt::Debug,
+{
+    debug_fmt_fields!(NoneWithProbability, iter, head, probability);
+}
+
+pub fn none_with_probability<I>(
+    iter: I,
+    probability: f64,
+    seed: u64,
+    head: usize,
+) -> NoneWithProbability<I>
+where
+    I: Iterator,
+{
+    NoneWithProbability {
+        iter,
+        head,
+        probability,
+        rand: Philox::new(seed),
+    }
+}
+
+impl<I> Iterator for NoneWithProbability<I>
+where
+    I: Iterator,
+{
+    type Item = Option<I::Item>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let v = self.iter.next()?;
+        let u = (self.rand.row(self.head as u64).next_u64() >> 11) as f64 / (1u64 << 53) as f64;
+        self.head += 1;
+        if u < self.probability {
+            Some(None)
+        } else {
+            Some(Some(v))
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+
+    fn count(self) -> usize {
+        self.iter.count()
+    }
+}
+
+impl<I> FusedIterator for NoneWithProbability<I> where I: FusedIterator {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const N: usize = 1_000_003;
+    const K: usize = 123_457;
+    const SEED: u64 = 42;
+
+    fn nas(start: usize, end: usize) -> Vec<usize> {
+        none_by_sample(start..end, K, N, SEED, start)
+            .zip(start..end)
+            .filter_map(|(v, i)| v.is_none().then_some(i))
+            .collect()
+    }
+
+    #[test]
+    fn sample_has_exact_count() {
+        let full = nas(0, N);
+        assert_eq!(full.len(), K);
+        assert!(full.windows(2).all(|w| w[0] < w[1]));
+        let all = none_by_sample(0..100, 100, 100, SEED, 0).filter(Option::is_none);
+        assert_eq!(all.count(), 100);
+        let none = none_by_sample(0..100, 0, 100, SEED, 0).filter(Option::is_none);
+        assert_eq!(none.count(), 0);
+    }
+
+    #[test]
+    fn sample_from_head_matches_full_run() {
+        let full = nas(0, N);
+        for start in [1, 4095, 4096, 4097, 500_000, 999_999, N] {
+            let expected = full
+                .iter()
+                .copied()
+                .filter(|&i| i >= start)
+                .collect::<Vec<_>>();
+            assert_eq!(nas(start, N), expected, "start {}", start);
+        }
+    }
+
+    #[test]
+    fn sample_chunks_match_full_run() {
+        let full = nas(0, N);
+        let mut chunked = vec![];
+        let mut start = 0;
+        for size in [1, 4095, 4097, 12_345, 250_000].iter().cycle() {
+            if start == N {
+                break;
+            }
+            let end = (start + size).min(N);
+            chunked.extend(nas(start, end));
+            start = end;
+        }
+        assert_eq!(chunked, full);
+    }
+
+    #[test]
+    fn sample_is_uniform() {
+        let full = nas(0, N);
+        let expected = K as f64 / 8.0;
+        for part in 0..8 {
+            let range = part * N / 8..(part + 1) * N / 8;
+            let count = full.iter().filter(|i| range.contains(i)).count() as f64;
+            assert!(
+                (count - expected).abs() < 6.0 * expected.sqrt(),
+                "part {}",
+                part
+            );
+        }
+        let seeds = (0..2000)
+            .map(|seed| none_by_sample(0..10, 1, 10, seed, 0).position(|v| v.is_none()))
+            .fold([0; 10], |mut counts, i| {
+                counts[i.unwrap()] += 1;
+                counts
+            });
+        assert!(
+            seeds.iter().all(|&c| (140..=260).contains(&c)),
+            "{:?}",
+            seeds
+        );
+    }
+}
@@ -0,0 +1,200 @@
+use crate::iter::KeySet;
+use crate::utils::rand::{rewind as rewind_rand, Rand, RandRange};
+use std::cmp;
+use std::collections::BinaryHeap;
+use std::ops::Range;
+
+struct Keyed<T> {
+    key: f64,
+    item: T,
+}
+
+impl<T> PartialEq for Keyed<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.key.total_cmp(&other.key) == cmp::Ordering::Equal
+    }
+}
+
+impl<T> Eq for Keyed<T> {}
+
+impl<T> PartialOrd for Keyed<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T> Ord for Keyed<T> {
+    fn cmp(&self, other: &Self) -> cmp::Ordering {
+        other.key.total_cmp(&self.key)
+    }
+}
+
+fn unit<R: Rand>(rand: &mut R) -> f64 {
+    ((rand.next_u64() >> 11) as f64 + 0.5) / (1u64 << 53) as f64
+}
+
+pub fn reservoir<I>(mut iter: I, amount: usize, seed: u64) -> Vec<(usize, I::Item)>
+where
+    I: Iterator,
+{
+    if amount < 1 {
+        return Vec::new();
+    }
+    let mut rand = rewind_rand(seed);
+    let mut reservoir = Vec::with_capacity(amount);
+    reservoir.extend(iter.by_ref().take(amount).enumerate());
+    if reservoir.len() < amount {
+        reservoir.shrink_to_fit();
+        return reservoir;
+    }
+    let k = amount as f64;
+    let mut w = (unit(&mut rand).ln() / k).exp();
+    let mut position = amount;
+    loop {
+        let skip = (unit(&mut rand).ln() / (-w).ln_1p()).floor();
+        if skip >= (usize::MAX - position) as f64 {
+            break;
+        }
+        let skip = skip as usize;
+        match iter.nth(skip) {
+            Some(e) => {
+                position += skip;
+                reservoir[rand.next_range(0..amount)] = (position, e);
+                position += 1;
+            }
+            None => break,
+        }
+        w *= (unit(&mut rand).ln() / k).exp();
+    }
+    reservoir
+}
+
+pub fn choose_range_seeded(domain: Range<u64>, amount: usize, seed: u64) -> KeySet<u64> {
+    let size = domain.end.saturating_sub(domain.start);
+    let amount = (amount as u64).min(size);
+    let mut rand = rewind_rand(seed);
+    let mut chosen = KeySet::with_capacity_and_hasher(amount as usize, Default::default());
+    for j in size - amount..size {
+        let t = domain.start + rand.next_range(0..j + 1);
+        if !chosen.insert(t) {
+            chosen.insert(domain.start + j);
+        }
+    }
+    chosen
+}
+
+pub fn weighted_reservoir<I, F>(
+    iter: I,
+    amount: usize,
+    seed: u64,
+    mut weight: F,
+) -> Vec<(usize, I::Item)>
+where
+    I: Iterator,
+    F: FnMut(&I::Item) -> f64,
+{
+    let mut rand = rewind_rand(seed);
+    let mut reservoir = BinaryHeap::with_capacity(amount);
+    if amount > 0 {
+        for (i, item) in iter.enumerate() {
+            let w = weight(&item);
+            if !(w > 0.0 && w.is_finite()) {
+                continue;
+            }
+            let key = unit(&mut rand).ln() / w;
+            if reservoir.len() < amount {
+                reservoir.push(Keyed {
+                    key,
+                    item: (i, item),
+                });
+            } else if reservoir.peek().is_some_and(|min: &Keyed<_>| key > min.key) {
+                reservoir.pop();
+                reservoir.push(Keyed {
+                    key,
+                    item: (i, item),
+                });
+            }
+        }
+    }
+    reservoir.into_iter().map(|k| k.item).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reservoir_keeps_distinct_positions() {
+        for (n, amount) in [
+            (0, 5),
+            (3, 5),
+            (5, 5),
+            (1000, 1),
+            (100_000, 37),
+            (100_000, 5000),
+        ] {
+            let sample = reservoir((0..n).map(|i| i * 3), amount, 7);
+            assert_eq!(sample.len(), amount.min(n));
+            assert!(sample.iter().all(|&(i, v)| i < n && v == i * 3));
+            let positions = sample.iter().map(|&(i, _)| i).collect::<KeySet<_>>();
+            assert_eq!(positions.len(), sample.len());
+            assert_eq!(reservoir((0..n).map(|i| i * 3), amount, 7), sample);
+        }
+        assert!(reservoir(0..10, 0, 7).is_empty());
+    }
+
+    #[test]
+    fn reservoir_is_uniform() {
+        let mut counts = [0; 10];
+        for seed in 0..5000 {
+            for (i, _) in reservoir(0..10, 3, seed) {
+                counts[i] += 1;
+            }
+        }
+        assert!(
+            counts.iter().all(|&c| (1350..=1650).contains(&c)),
+            "{:?}",
+            counts
+        );
+        let mut halves = [0; 2];
+        for seed in 0..200 {
+            for (i, _) in reservoir(0..100_000, 50, seed) {
+                halves[i / 50_000] += 1;
+            }
+        }
+        assert!(
+            halves.iter().all(|&c| (4700..=5300).contains(&c)),
+            "{:?}",
+            halves
+        );
+    }
+
+    #[test]
+    fn choose_range_stays_in_domain() {
+        for (domain, amount) in [(0..0, 3), (5..8, 10), (10..20, 10), (100..1_000_000, 1000)] {
+            let chosen = choose_range_seeded(domain.clone(), amount, 11);
+            let size = (domain.end - domain.start) as usize;
+            assert_eq!(chosen.len(), amount.min(size));
+            assert!(chosen.iter().all(|v| domain.contains(v)));
+            assert_eq!(choose_range_seeded(domain, amount, 11), chosen);
+        }
+        let top = choose_range_seeded(u64::MAX - 10..u64::MAX, 4, 11);
+        assert_eq!(top.len(), 4);
+        assert!(top.iter().all(|&v| v >= u64::MAX - 10));
+    }
+
+    #[test]
+    fn choose_range_is_uniform() {
+        let mut counts = [0; 10];
+        for seed in 0..5000 {
+            for v in choose_range_seeded(20..30, 3, seed) {
+                counts[v as usize - 20] += 1;
+            }
+        }
+        assert!(
+            counts.iter().all(|&c| (1350..=1650).contains(&c)),
+            "{:?}",
+            counts
+        );
+    }
+}
@@ -1,4 +1,5 @@
 use crate::debug_fmt_fields;
+use crate::iter::approx::BloomFilter;
 use crate::iter::{KeyEntry, KeySet};
 use std::fmt;
 use std::hash::Hash;
@@ -39,7 +40,7 @@ where
 {
     let iter = iterable.into_iter();
     let current_used = used.len();
-    used.extend(iter.map(|v| v));
+    used.extend(iter);
     used.len() - current_used
 }
 
@@ -52,7 +53,7 @@ where
     type Item = I::Item;
 
     fn next(&mut self) -> Option<Self::Item> {
-        while let Some(v) = self.iter.next() {
+        for v in self.iter.by_ref() {
             let k = (self.f)(&v);
             if self.used.insert(k) {
                 return Some(v);
@@ -133,7 +134,7 @@ where
     type Item = I::Item;
 
     fn next(&mut self) -> Option<Self::Item> {
-        while let Some(v) = self.iter.iter.next() {
+        for v in self.iter.iter.by_ref() {
             if let KeyEntry::Vacant(e) = self.iter.used.entry(v) {
                 let k = e.get().clone();
                 e.insert();
@@ -177,3 +178,110 @@ where
     I::Item: Eq + Hash + Clone,
 {
 }
+
+#[derive(Clone)]
+pub struct ApproxUniqueBy<I: Iterator, F> {
+    iter: I,
+    used: BloomFilter,
+    f: F,
+}
+
+impl<I, F> fmt::Debug for ApproxUniqueBy<I, F>
+where
+    I: Iterator + fmt::Debug,
+{
+    debug_fmt_fields!(ApproxUniqueBy, iter, used);
+}
+
+pub fn approx_unique_by<I, V, F>(
+    iter: I,
+    expected: usize,
+    false_positive_rate: f64,
+    f: F,
+) -> ApproxUniqueBy<I, F>
+where
+    V: Hash,
+    F: FnMut(&I::Item) -> V,
+    I: Iterator,
+{
+    ApproxUniqueBy {
+        iter,
+        used: BloomFilter::new(expected, false_positive_rate),
+        f,
+    }
+}
+
+impl<I, V, F> Iterator for ApproxUniqueBy<I, F>
+where
+    I: Iterator,
+    V: Hash,
+    F: FnMut(&I::Item) -> V,
+{
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (used, f) = (&mut self.used, &mut self.f);
+        self.iter.find(|v| used.insert(&f(v)))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, self.iter.size_hint().1)
+    }
+}
+
+impl<I, V, F> FusedIterator for ApproxUniqueBy<I, F>
+where
+    I: FusedIterator,
+    V: Hash,
+    F: FnMut(&I::Item) -> V,
+{
+}
+
+#[derive(Clone)]
+pub struct ApproxUnique<I: Iterator> {
+    iter: I,
+    used: BloomFilter,
+}
+
+impl<I> fmt::Debug for ApproxUnique<I>
+where
+    I: Iterator + fmt::Debug,
+{
+    debug_fmt_fields!(ApproxUnique, iter, used);
+}
+
+pub fn approx_unique<I>(iter: I, expected: usize, false_positive_rate: f64) -> ApproxUnique<I>
+where
+    I: Iterator,
+    I::Item: Hash,
+{
+    ApproxUnique {
+        iter,
+        used: BloomFilter::new(expected, false_positive_rate),
+    }
+}
+
+impl<I> Iterator for ApproxUnique<I>
+where
+    I: Iterator,
+    I::Item: Hash,
+{
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.by_ref().find(|v| self.used.insert(v))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, self.iter.size_hint().1)
+    }
+}
+
+impl<I> FusedIterator for ApproxUnique<I>
+where
+    I: FusedIterator,
+    I::Item: Hash,
+{
+}
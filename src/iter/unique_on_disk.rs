@@ -0,0 +1,304 @@
+use crate::debug_fmt_fields;
+use crate::iter::KeySet;
+use crate::utils::memory::get_default_sort_size;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::fmt;
+use std::fs;
+use std::hash::Hash;
+use std::io;
+use std::iter::FusedIterator;
+use std::mem;
+use std::path::PathBuf;
+
+const FANOUT: usize = 64;
+const MAX_LEVEL: u32 = 8;
+const MIN_BUDGET: u64 = 64 * 1024 * 1024;
+
+type Entries<V> = Box<dyn Iterator<Item = csv::Result<(bool, V)>>>;
+
+struct ByteCount(u64);
+
+impl io::Write for ByteCount {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0 += buf.len() as u64;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+#[derive(Debug)]
+struct Bucket {
+    level: u32,
+    seen: PathBuf,
+    new: PathBuf,
+}
+
+pub struct UniqueOnDisk<I: Iterator> {
+    iter: I,
+    drained: bool,
+    used: KeySet<I::Item>,
+    heap: u64,
+    sizer: csv::Writer<ByteCount>,
+    budget: u64,
+    level: u32,
+    entries: Option<Entries<I::Item>>,
+    buckets: Vec<Bucket>,
+    spills: usize,
+    dir: Option<tempfile::TempDir>,
+}
+
+impl<I> fmt::Debug for UniqueOnDisk<I>
+where
+    I: Iterator + fmt::Debug,
+{
+    debug_fmt_fields!(UniqueOnDisk, iter, budget, level, buckets);
+}
+
+pub fn unique_on_disk<I>(iter: I, budget: Option<u64>) -> UniqueOnDisk<I>
+where
+    I: Iterator,
+    I::Item: Eq + Hash,
+{
+    UniqueOnDisk {
+        iter,
+        drained: false,
+        used: KeySet::with_capacity_and_hasher(0, Default::default()),
+        heap: 0,
+        sizer: csv::WriterBuilder::new()
+            .has_headers(false)
+            .flexible(true)
+            .quote_style(csv::QuoteStyle::Necessary)
+            .from_writer(ByteCount(0)),
+        budget: budget.unwrap_or_else(|| get_default_sort_size(MIN_BUDGET)),
+        level: 0,
+        entries: None,
+        buckets: vec![],
+        spills: 0,
+        dir: None,
+    }
+}
+
+fn partition<V: Hash>(level: u32, value: &V) -> usize {
+    let hasher = ahash::RandomState::with_seeds(level as u64, 0, 0, 0);
+    (hasher.hash_one(value) % FANOUT as u64) as usize
+}
+
+fn bucket_writer(path: &PathBuf) -> io::Result<csv::Writer<fs::File>> {
+    Ok(csv::WriterBuilder::new()
+        .has_headers(false)
+        .flexible(true)
+        .quote_style(csv::QuoteStyle::Necessary)
+        .from_writer(fs::File::create(path)?))
+}
+
+fn bucket_reader<V: DeserializeOwned + 'static>(
+    path: &PathBuf,
+    seen: bool,
+) -> io::Result<impl Iterator<Item = csv::Result<(bool, V)>>> {
+    let file = fs::File::open(path)?;
+    fs::remove_file(path)?;
+    Ok(csv::ReaderBuilder::new()
+        .has_headers(false)
+        .flexible(true)
+        .from_reader(file)
+        .into_deserialize()
+        .map(move |v| v.map(|v| (seen, v))))
+}
+
+impl<I> UniqueOnDisk<I>
+where
+    I: Iterator,
+    I::Item: Eq + Hash + Clone + Serialize + DeserializeOwned + 'static,
+{
+    fn next_entry(&mut self) -> Option<csv::Result<(bool, I::Item)>> {
+        match self.entries {
+            Some(ref mut entries) => entries.next(),
+            None if !self.drained => {
+                let entry = self.iter.next();
+                self.drained = entry.is_none();
+                entry.map(|v| Ok((false, v)))
+            }
+            None => None,
+        }
+    }
+
+    fn insert(&mut self, v: I::Item) -> csv::Result<()> {
+        let before = self.sizer.get_ref().0;
+        self.sizer.serialize(&v)?;
+        self.sizer.flush()?;
+        self.heap += self.sizer.get_ref().0 - before;
+        self.used.insert(v);
+        Ok(())
+    }
+
+    fn over_budget(&self) -> bool {
+        let table = self.used.capacity() * (mem::size_of::<I::Item>() + 1);
+        table as u64 + self.heap > self.budget && self.level < MAX_LEVEL
+    }
+
+    fn spill(&mut self) -> csv::Result<()> {
+        if self.dir.is_none() {
+            self.dir = Some(tempfile::tempdir()?);
+        }
+        let level = self.level + 1;
+        let base = self.dir.as_ref().map(|d| d.path().to_path_buf());
+        let base = base.unwrap_or_default();
+        let mut buckets = Vec::with_capacity(FANOUT);
+        let mut seen_writers = Vec::with_capacity(FANOUT);
+        let mut new_writers = Vec::with_capacity(FANOUT);
+        for b in 0..FANOUT {
+            let bucket = Bucket {
+                level,
+                seen: base.join(format!("{}-{}.seen.csv", self.spills, b)),
+                new: base.join(format!("{}-{}.new.csv", self.spills, b)),
+            };
+            seen_writers.push(bucket_writer(&bucket.seen)?);
+            new_writers.push(bucket_writer(&bucket.new)?);
+            buckets.push(bucket);
+        }
+        self.spills += 1;
+        let used = mem::replace(
+            &mut self.used,
+            KeySet::with_capacity_and_hasher(0, Default::default()),
+        );
+        self.heap = 0;
+        for v in used {
+            seen_writers[partition(level, &v)].serialize(&v)?;
+        }
+        let mut has_new = [false; FANOUT];
+        while let Some(entry) = self.next_entry() {
+            let (seen, v) = entry?;
+            let b = partition(level, &v);
+            if seen {
+                seen_writers[b].serialize(&v)?;
+            } else {
+                new_writers[b].serialize(&v)?;
+                has_new[b] = true;
+            }
+        }
+        self.entries = None;
+        self.drained = true;
+        let writers = seen_writers.into_iter().zip(new_writers);
+        for (b, (bucket, (mut seen, mut new))) in buckets.into_iter().zip(writers).enumerate() {
+            seen.flush()?;
+            new.flush()?;
+            drop((seen, new));
+            if has_new[b] {
+                self.buckets.push(bucket);
+            } else {
+                fs::remove_file(&bucket.seen)?;
+                fs::remove_file(&bucket.new)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn open_next_bucket(&mut self) -> csv::Result<bool> {
+        self.used = KeySet::with_capacity_and_hasher(0, Default::default());
+        self.heap = 0;
+        self.entries = None;
+        let Some(bucket) = self.buckets.pop() else {
+            return Ok(false);
+        };
+        self.level = bucket.level;
+        let seen = bucket_reader(&bucket.seen, true)?;
+        let new = bucket_reader(&bucket.new, false)?;
+        self.entries = Some(Box::new(seen.chain(new)));
+        Ok(true)
+    }
+}
+
+impl<I> Iterator for UniqueOnDisk<I>
+where
+    I: Iterator,
+    I::Item: Eq + Hash + Clone + Serialize + DeserializeOwned + 'static,
+{
+    type Item = csv::Result<I::Item>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let (seen, v) = match self.next_entry() {
+                Some(Ok(entry)) => entry,
+                Some(Err(err)) => return Some(Err(err)),
+                None => match self.open_next_bucket() {
+                    Ok(true) => continue,
+                    Ok(false) => return None,
+                    Err(err) => return Some(Err(err)),
+                },
+            };
+            let emitted = if self.used.contains(&v) {
+                Ok(None)
+            } else if seen {
+                self.insert(v).map(|_| None)
+            } else {
+                self.insert(v.clone()).map(|_| Some(v))
+            };
+            let emitted = match emitted {
+                Ok(emitted) => emitted,
+                Err(err) => return Some(Err(err)),
+            };
+            if self.over_budget() {
+                if let Err(err) = self.spill() {
+                    return Some(Err(err));
+                }
+            }
+            if let Some(v) = emitted {
+                return Some(Ok(v));
+            }
+        }
+    }
+}
+
+impl<I> FusedIterator for UniqueOnDisk<I>
+where
+    I: Iterator,
+    I::Item: Eq + Hash + Clone + Serialize + DeserializeOwned + 'static,
+{
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::iter::extensions::UniqueValueIterator;
+
+    fn values() -> impl Iterator<Item = u64> {
+        (0..100_000u64).map(|i| i.wrapping_mul(0x9e3779b97f4a7c15) % 60_000)
+    }
+
+    #[test]
+    fn unique_on_disk_matches_unique_in_budget() {
+        let expected = values().unique().collect::<Vec<_>>();
+        let actual = values().unique_on_disk().collect::<csv::Result<Vec<_>>>();
+        assert_eq!(actual.unwrap(), expected);
+    }
+
+    #[test]
+    fn unique_on_disk_matches_unique_over_budget() {
+        let expected = values().unique().collect::<KeySet<_>>();
+        let mut iter = values().unique_on_disk_with_budget(1 << 12);
+        let (mut actual, mut deepest) = (vec![], 0);
+        while let Some(v) = iter.next() {
+            actual.push(v.unwrap());
+            deepest = deepest.max(iter.level);
+        }
+        assert!(deepest >= 2, "deepest level {}", deepest);
+        assert_eq!(actual.len(), expected.len());
+        assert_eq!(actual.into_iter().collect::<KeySet<_>>(), expected);
+    }
+
+    #[test]
+    fn unique_on_disk_stops_at_max_level() {
+        let mut iter = (0..20u64).unique_on_disk_with_budget(1);
+        let mut actual = vec![];
+        while let Some(v) = iter.next() {
+            actual.push(v.unwrap());
+            assert!(iter.level <= MAX_LEVEL);
+        }
+        actual.sort_unstable();
+        assert_eq!(actual, (0..20).collect::<Vec<_>>());
+    }
+}
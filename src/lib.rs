@@ -1,5 +1,8 @@
+pub mod column;
 pub mod io;
 pub mod iter;
+pub mod schema;
+pub mod table;
 pub mod utils;
 
 #[macro_export]
@@ -0,0 +1,489 @@
+use crate::column::decimal::{self, Decimal};
+use crate::column::dependent::{Dependence, Dependent};
+use crate::column::float::ScaledFloat;
+use crate::column::id::FormattedId;
+use crate::column::integer::RandomInteger;
+use crate::column::number::RandomNumber;
+use crate::column::string::{self, Alphabet, RandomString};
+use crate::column::temporal::{Granularity, Temporal, TemporalFormat};
+use crate::column::uid::UniqueId;
+use crate::column::{ColumnGenerator, LatentColumn, Nas};
+use crate::table::Table;
+use crate::utils::rand::distribution::{Distribution, Skew};
+use chrono::{DateTime, NaiveDate, NaiveTime, TimeDelta, Utc};
+use chrono_tz::Tz;
+use serde::Deserialize;
+use std::fs;
+use std::io;
+use std::ops::Range;
+use std::path::Path;
+use std::str::FromStr;
+
+#[derive(Debug, Deserialize)]
+pub struct Schema {
+    #[serde(rename = "table")]
+    pub tables: Vec<TableSchema>,
+}
+
+impl Schema {
+    pub fn from_path<P: AsRef<Path>>(path: P) -> io::Result<Schema> {
+        let path = path.as_ref();
+        match fs::read_to_string(path) {
+            Ok(s) => s.parse(),
+            Err(err) => {
+                let msg = format!("failed to open {}: {}", path.display(), err);
+                Err(io::Error::new(io::ErrorKind::NotFound, msg))
+            }
+        }
+    }
+}
+
+impl FromStr for Schema {
+    type Err = io::Error;
+
+    fn from_str(s: &str) -> io::Result<Schema> {
+        toml::from_str(s).map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+    }
+}
+
+fn default_run_size() -> usize {
+    1024 * 1024
+}
+
+#[derive(Debug, Deserialize)]
+pub struct TableSchema {
+    pub name: String,
+    pub rows: usize,
+    pub output: Option<String>,
+    #[serde(default)]
+    pub headers: bool,
+    #[serde(default)]
+    pub sort: Vec<String>,
+    #[serde(default = "default_run_size")]
+    pub run_size: usize,
+    #[serde(rename = "column")]
+    pub columns: Vec<ColumnSchema>,
+}
+
+impl TableSchema {
+    pub fn output(&self) -> String {
+        self.output
+            .clone()
+            .unwrap_or_else(|| format!("{}.csv", self.name))
+    }
+
+    pub fn table(&self) -> io::Result<Table> {
+        self.columns.iter().try_fold(
+            Table::new(&self.name, self.rows)
+                .sort_keys(&self.sort)
+                .run_size(self.run_size),
+            |table, column| Ok(table.boxed_column(column.generator(self)?)),
+        )
+    }
+}
+
+fn default_prefix() -> String {
+    String::from("id")
+}
+
+fn default_width() -> usize {
+    3
+}
+
+fn default_precision() -> usize {
+    6
+}
+
+fn default_length() -> Distribution {
+    Distribution::Uniform {
+        low: 8.0,
+        high: 16.0,
+    }
+}
+
+fn default_max_length() -> usize {
+    string::DEFAULT_MAX_LENGTH
+}
+
+fn default_version() -> u8 {
+    4
+}
+
+fn default_interval() -> i64 {
+    1
+}
+
+fn default_granularity() -> Granularity {
+    Granularity::Second
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ColumnKind {
+    Integer {
+        min: u64,
+        max: u64,
+        #[serde(default)]
+        skew: Skew,
+    },
+    Id {
+        min: u64,
+        max: u64,
+        #[serde(default = "default_prefix")]
+        prefix: String,
+        #[serde(default = "default_width")]
+        width: usize,
+        #[serde(default)]
+        skew: Skew,
+    },
+    Float {
+        min: u64,
+        max: u64,
+        scale: u64,
+        #[serde(default = "default_precision")]
+        precision: usize,
+    },
+    Number {
+        distribution: Distribution,
+        #[serde(default = "default_precision")]
+        precision: usize,
+    },
+    Decimal {
+        precision: u32,
+        scale: u32,
+        min: Option<String>,
+        max: Option<String>,
+    },
+    Uuid {
+        #[serde(default = "default_version")]
+        version: u8,
+        start: Option<DateTime<Utc>>,
+        #[serde(default = "default_interval")]
+        interval: i64,
+    },
+    Ulid {
+        start: Option<DateTime<Utc>>,
+        #[serde(default = "default_interval")]
+        interval: i64,
+    },
+    String {
+        #[serde(default = "default_length")]
+        length: Distribution,
+        #[serde(default = "default_max_length")]
+        max_length: usize,
+        #[serde(default)]
+        alphabet: Alphabet,
+        template: Option<String>,
+    },
+    Date {
+        min: NaiveDate,
+        max: NaiveDate,
+        #[serde(default)]
+        format: TemporalFormat,
+    },
+    Time {
+        min: NaiveTime,
+        max: NaiveTime,
+        #[serde(default = "default_granularity")]
+        granularity: Granularity,
+        #[serde(default)]
+        format: TemporalFormat,
+    },
+    Timestamp {
+        min: DateTime<Utc>,
+        max: DateTime<Utc>,
+        #[serde(default = "default_granularity")]
+        granularity: Granularity,
+        #[serde(default)]
+        time_zone: Tz,
+        #[serde(default)]
+        format: TemporalFormat,
+    },
+    Interval {
+        min: i64,
+        max: i64,
+        #[serde(default = "default_granularity")]
+        granularity: Granularity,
+        #[serde(default)]
+        format: TemporalFormat,
+    },
+    Dependent {
+        on: String,
+        dependence: Dependence,
+        #[serde(default = "default_precision")]
+        precision: usize,
+    },
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ColumnSchema {
+    pub name: String,
+    #[serde(flatten)]
+    pub kind: ColumnKind,
+    #[serde(default)]
+    pub nas: Nas,
+}
+
+fn inclusive(name: &str, min: u64, max: u64) -> io::Result<Range<u64>> {
+    if min > max {
+        let msg = format!("min of {} is greater than max: {} > {}", name, min, max);
+        return Err(io::Error::new(io::ErrorKind::InvalidInput, msg));
+    }
+    match max.checked_add(1) {
+        Some(end) => Ok(min..end),
+        None => {
+            let msg = format!("max of {} is out of range: {}", name, max);
+            Err(io::Error::new(io::ErrorKind::InvalidInput, msg))
+        }
+    }
+}
+
+fn temporal(column: Temporal) -> io::Result<Temporal> {
+    column.validate()?;
+    Ok(column)
+}
+
+impl ColumnSchema {
+    pub fn generator(&self, table: &TableSchema) -> io::Result<Box<dyn ColumnGenerator>> {
+        self.nas.validate()?;
+        match self.kind {
+            ColumnKind::Decimal {
+                precision,
+                scale,
+                ref min,
+                ref max,
+            } => {
+                if precision == 0 || precision > decimal::MAX_PRECISION || scale > precision {
+                    let msg = format!("invalid DECIMAL({}, {}) of {}", precision, scale, self.name);
+                    return Err(io::Error::new(io::ErrorKind::InvalidInput, msg));
+                }
+                let mut column = Decimal::new(&self.name, precision, scale).nas(self.nas);
+                if min.is_some() || max.is_some() {
+                    let limit = 10i128.pow(precision) - 1;
+                    let min = match min {
+                        Some(min) => decimal::unscaled(min, scale)?,
+                        None => -limit,
+                    };
+                    let max = match max {
+                        Some(max) => decimal::unscaled(max, scale)?,
+                        None => limit,
+                    };
+                    column = column.range(min..max + 1);
+                }
+                column.validate()?;
+                Ok(Box::new(column))
+            }
+            ColumnKind::Uuid {
+                version,
+                start,
+                interval,
+            } => {
+                let column = match version {
+                    4 => UniqueId::uuid_v4(&self.name),
+                    7 => UniqueId::uuid_v7(&self.name),
+                    _ => {
+                        let msg = format!("unsupported UUID version {} of {}", version, self.name);
+                        return Err(io::Error::new(io::ErrorKind::InvalidInput, msg));
+                    }
+                };
+                let column = match start {
+                    Some(start) => column.start(start),
+                    None => column,
+                };
+                self.unique_id(column, interval, table)
+            }
+            ColumnKind::Ulid { start, interval } => {
+                let column = UniqueId::ulid(&self.name);
+                let column = match start {
+                    Some(start) => column.start(start),
+                    None => column,
+                };
+                self.unique_id(column, interval, table)
+            }
+            ColumnKind::String {
+                length,
+                max_length,
+                ref alphabet,
+                ref template,
+            } => {
+                length.validate()?;
+                alphabet.validate()?;
+                let column = RandomString::new(&self.name)
+                    .length(length)
+                    .max_length(max_length)
+                    .alphabet(alphabet.clone())
+                    .nas(self.nas);
+                let column = match template {
+                    Some(template) => column.template(template.parse()?),
+                    None => column,
+                };
+                column.validate()?;
+                Ok(Box::new(column))
+            }
+            ColumnKind::Dependent {
+                ref on,
+                ref dependence,
+                precision,
+            } => {
+                dependence.validate()?;
+                let parent = match table.columns.iter().find(|c| &c.name == on) {
+                    Some(parent) => parent.latent()?,
+                    None => {
+                        let msg = format!("unknown column {} in table {}", on, table.name);
+                        return Err(io::Error::new(io::ErrorKind::InvalidInput, msg));
+                    }
+                };
+                if let Dependence::Linear { .. } = dependence {
+                    let (_, variance) = parent.latent(0).mean_variance();
+                    if !variance.is_finite() {
+                        let msg = format!("column {} has no finite variance", on);
+                        return Err(io::Error::new(io::ErrorKind::InvalidInput, msg));
+                    }
+                }
+                Ok(Box::new(
+                    Dependent::boxed(&self.name, parent, dependence.clone())
+                        .precision(precision)
+                        .nas(self.nas),
+                ))
+            }
+            _ => Ok(self.latent()?),
+        }
+    }
+
+    fn unique_id(
+        &self,
+        column: UniqueId,
+        interval: i64,
+        table: &TableSchema,
+    ) -> io::Result<Box<dyn ColumnGenerator>> {
+        let interval = TimeDelta::try_milliseconds(interval).ok_or_else(|| {
+            let msg = format!("interval of {} is out of range: {}", self.name, interval);
+            io::Error::new(io::ErrorKind::InvalidInput, msg)
+        })?;
+        let column = column.interval(interval).nas(self.nas);
+        column.validate(table.rows)?;
+        Ok(Box::new(column))
+    }
+
+    fn latent(&self) -> io::Result<Box<dyn LatentColumn>> {
+        Ok(match self.kind {
+            ColumnKind::Integer { min, max, skew } => {
+                skew.validate()?;
+                Box::new(
+                    RandomInteger::new(&self.name, inclusive(&self.name, min, max)?)
+                        .skew(skew)
+                        .nas(self.nas),
+                )
+            }
+            ColumnKind::Id {
+                min,
+                max,
+                ref prefix,
+                width,
+                skew,
+            } => {
+                skew.validate()?;
+                Box::new(
+                    FormattedId::new(&self.name, inclusive(&self.name, min, max)?)
+                        .prefix(prefix)
+                        .width(width)
+                        .skew(skew)
+                        .nas(self.nas),
+                )
+            }
+            ColumnKind::Float {
+                min,
+                max,
+                scale,
+                precision,
+            } => {
+                if scale == 0 {
+                    let msg = format!("scale of {} must be positive", self.name);
+                    return Err(io::Error::new(io::ErrorKind::InvalidInput, msg));
+                }
+                Box::new(
+                    ScaledFloat::new(&self.name, inclusive(&self.name, min, max)?, scale)
+                        .precision(precision)
+                        .nas(self.nas),
+                )
+            }
+            ColumnKind::Number {
+                distribution,
+                precision,
+            } => {
+                distribution.validate()?;
+                Box::new(
+                    RandomNumber::new(&self.name, distribution)
+                        .precision(precision)
+                        .nas(self.nas),
+                )
+            }
+            ColumnKind::Date {
+                min,
+                max,
+                ref format,
+            } => Box::new(temporal(
+                Temporal::date(&self.name, min..max)
+                    .inclusive()
+                    .format(format.clone())
+                    .nas(self.nas),
+            )?),
+            ColumnKind::Time {
+                min,
+                max,
+                granularity,
+                ref format,
+            } => Box::new(temporal(
+                Temporal::time(&self.name, min..max)
+                    .granularity(granularity)
+                    .inclusive()
+                    .format(format.clone())
+                    .nas(self.nas),
+            )?),
+            ColumnKind::Timestamp {
+                min,
+                max,
+                granularity,
+                time_zone,
+                ref format,
+            } => Box::new(temporal(
+                Temporal::timestamp(&self.name, min..max)
+                    .granularity(granularity)
+                    .inclusive()
+                    .time_zone(time_zone)
+                    .format(format.clone())
+                    .nas(self.nas),
+            )?),
+            ColumnKind::Interval {
+                min,
+                max,
+                granularity,
+                ref format,
+            } => {
+                let delta = |v: i64| {
+                    v.checked_mul(granularity.millis())
+                        .and_then(TimeDelta::try_milliseconds)
+                        .ok_or_else(|| {
+                            let msg = format!("interval of {} is out of range: {}", self.name, v);
+                            io::Error::new(io::ErrorKind::InvalidInput, msg)
+                        })
+                };
+                Box::new(temporal(
+                    Temporal::interval(&self.name, delta(min)?..delta(max)?)
+                        .granularity(granularity)
+                        .inclusive()
+                        .format(format.clone())
+                        .nas(self.nas),
+                )?)
+            }
+            ColumnKind::Decimal { .. }
+            | ColumnKind::Uuid { .. }
+            | ColumnKind::Ulid { .. }
+            | ColumnKind::String { .. }
+            | ColumnKind::Dependent { .. } => {
+                let msg = format!("column {} cannot be a parent", self.name);
+                return Err(io::Error::new(io::ErrorKind::InvalidInput, msg));
+            }
+        })
+    }
+}
@@ -0,0 +1,224 @@
+use crate::column::ColumnGenerator;
+use crate::io::config::Config;
+use crate::io::manipulate::{dump, hstack, merge_sort_by, sort_chunk_by, zip};
+use crate::utils::rand::{init as init_rand, split as split_seed};
+use crate::utils::thread::par_map;
+use std::fs;
+use std::io;
+use std::io::Seek;
+use std::ops::Range;
+
+const PROGRESS_STEP: u64 = 1 << 16;
+
+pub struct Table {
+    name: String,
+    number_of_rows: usize,
+    columns: Vec<Box<dyn ColumnGenerator>>,
+    sort_keys: Vec<String>,
+    run_size: usize,
+    seed: u64,
+    threads: usize,
+    shard: (usize, usize),
+    progress: Option<Box<dyn Fn(u64) + Send + Sync>>,
+}
+
+impl Table {
+    pub fn new(name: &str, number_of_rows: usize) -> Table {
+        Table {
+            name: name.to_string(),
+            number_of_rows,
+            columns: vec![],
+            sort_keys: vec![],
+            run_size: 1024 * 1024,
+            seed: init_rand().0,
+            threads: 1,
+            shard: (0, 1),
+            progress: None,
+        }
+    }
+
+    pub fn column<C: ColumnGenerator + 'static>(mut self, column: C) -> Table {
+        self.columns.push(Box::new(column));
+        self
+    }
+
+    pub fn boxed_column(mut self, column: Box<dyn ColumnGenerator>) -> Table {
+        self.columns.push(column);
+        self
+    }
+
+    pub fn sort_keys(mut self, keys: &[String]) -> Table {
+        self.sort_keys = keys.to_vec();
+        self
+    }
+
+    pub fn run_size(mut self, run_size: usize) -> Table {
+        self.run_size = run_size;
+        self
+    }
+
+    pub fn seed(mut self, seed: u64) -> Table {
+        self.seed = seed;
+        self
+    }
+
+    pub fn threads(mut self, threads: usize) -> Table {
+        self.threads = threads.max(1);
+        self
+    }
+
+    pub fn shard(mut self, index: usize, count: usize) -> Table {
+        self.shard = (index, count);
+        self
+    }
+
+    pub fn progress<F: Fn(u64) + Send + Sync + 'static>(mut self, progress: F) -> Table {
+        self.progress = Some(Box::new(progress));
+        self
+    }
+
+    fn report(&self, cells: u64) {
+        if let Some(ref progress) = self.progress {
+            progress(cells);
+        }
+    }
+
+    pub fn rows(&self) -> Range<usize> {
+        let (index, count) = self.shard;
+        let n = self.number_of_rows as u128;
+        let start = n * index as u128 / count.max(1) as u128;
+        let end = n * (index as u128 + 1) / count.max(1) as u128;
+        start as usize..end.min(n) as usize
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn number_of_rows(&self) -> usize {
+        self.number_of_rows
+    }
+
+    pub fn columns(&self) -> &[Box<dyn ColumnGenerator>] {
+        &self.columns
+    }
+
+    fn sort_key_indices(&self) -> io::Result<Vec<usize>> {
+        self.sort_keys
+            .iter()
+            .map(|key| {
+                self.columns
+                    .iter()
+                    .position(|c| c.name() == key)
+                    .ok_or_else(|| {
+                        let msg = format!("unknown sort key {} in table {}", key, self.name);
+                        io::Error::new(io::ErrorKind::InvalidInput, msg)
+                    })
+            })
+            .collect()
+    }
+
+    fn create_columns(&self, scratch: &Config) -> csv::Result<Vec<Box<dyn io::Read>>> {
+        let sources = par_map(self.threads, self.columns.len(), |i| {
+            let column = &self.columns[i];
+            column.source(split_seed(self.seed, column.stream()), self.number_of_rows)
+        });
+        let rows = self.rows();
+        let chunk_size = rows.len().div_ceil(self.threads).max(1);
+        let number_of_chunks = rows.len().div_ceil(chunk_size);
+        let chunks = par_map(
+            self.threads,
+            sources.len() * number_of_chunks,
+            |i| -> csv::Result<fs::File> {
+                let start = rows.start + i % number_of_chunks * chunk_size;
+                let end = rows.end.min(start + chunk_size);
+                let mut csv = tempfile::tempfile()?;
+                let mut writer = scratch.from_writer(&mut csv);
+                let mut pending = 0;
+                let cells = sources[i / number_of_chunks]
+                    .cells(start..end)
+                    .inspect(|_| {
+                        pending += 1;
+                        if pending == PROGRESS_STEP {
+                            self.report(pending);
+                            pending = 0;
+                        }
+                    });
+                dump(&mut writer, cells)?;
+                self.report(pending);
+                writer.flush()?;
+                drop(writer);
+                csv.rewind()?;
+                Ok(csv)
+            },
+        );
+        let mut chunks = chunks.into_iter();
+        let mut csvs = Vec::with_capacity(sources.len());
+        for _ in 0..sources.len() {
+            let mut csv: Box<dyn io::Read> = Box::new(io::empty());
+            for chunk in chunks.by_ref().take(number_of_chunks) {
+                csv = Box::new(io::Read::chain(csv, chunk?));
+            }
+            csvs.push(csv);
+        }
+        Ok(csvs)
+    }
+
+    pub fn write(&self, config: &Config) -> csv::Result<()> {
+        let (index, count) = self.shard;
+        if index >= count {
+            let msg = format!(
+                "invalid shard {} of {} in table {}",
+                index, count, self.name
+            );
+            return Err(io::Error::new(io::ErrorKind::InvalidInput, msg).into());
+        }
+        let keys = self.sort_key_indices()?;
+        if !keys.is_empty() && count > 1 {
+            let msg = format!("cannot shard sorted table {}", self.name);
+            return Err(io::Error::new(io::ErrorKind::InvalidInput, msg).into());
+        }
+        let scratch = Config::default()
+            .quote_style(csv::QuoteStyle::Necessary)
+            .double_quote(true);
+        let mut csvs = self.create_columns(&scratch)?;
+        let mut writer = config.writer()?;
+        if !config.no_headers && index == 0 {
+            writer.write_record(self.columns.iter().map(|c| c.name()))?;
+        }
+        let mut readers: Vec<_> = csvs.iter_mut().map(|f| scratch.from_reader(f)).collect();
+        let mut zipped_iter = zip(readers.iter_mut().collect());
+        if keys.is_empty() {
+            hstack(&mut writer, &mut zipped_iter)?;
+        } else if self.rows().len() <= self.run_size {
+            sort_chunk_by(&keys, None, &mut writer, &mut zipped_iter)?;
+        } else {
+            let working_dir = tempfile::tempdir()?;
+            let mut runs = vec![];
+            for i in 0..self.rows().len().div_ceil(self.run_size) {
+                let path = working_dir.path().join(format!("{}.csv", i));
+                let mut run_writer = scratch.from_writer(fs::File::create(&path)?);
+                sort_chunk_by(
+                    &keys,
+                    Some(self.run_size),
+                    &mut run_writer,
+                    &mut zipped_iter,
+                )?;
+                run_writer.flush()?;
+                runs.push(path);
+            }
+            drop(readers);
+            drop(csvs);
+            let mut runs = runs
+                .iter()
+                .map(|path| fs::File::open(path).map(|f| scratch.from_reader(f)))
+                .collect::<io::Result<Vec<_>>>()?;
+            merge_sort_by(&keys, &mut writer, &mut runs)?;
+        }
+        match writer.into_inner() {
+            Ok(encoder) => encoder.finish()?,
+            Err(err) => return Err(err.into_error().into()),
+        }
+        Ok(())
+    }
+}
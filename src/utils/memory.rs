@@ -5,7 +5,6 @@ use std::cell::RefCell;
 use std::cmp;
 use std::io;
 use std::sync::Mutex;
-use std::u64;
 use sysinfo::{System, SystemExt};
 
 static SYSTEM: Lazy<Mutex<RefCell<System>>> = Lazy::new(|| {
@@ -1,6 +1,8 @@
 use rand::Rng;
-pub use tinyrand::RandRange;
-use tinyrand::{Seeded, StdRand};
+use tinyrand::Seeded;
+
+pub mod distribution;
+pub use tinyrand::{Rand, RandRange, StdRand};
 
 pub fn init() -> (u64, StdRand) {
     let seed: u64 = rand::thread_rng().gen();
@@ -10,3 +12,132 @@ pub fn init() -> (u64, StdRand) {
 pub fn rewind(seed: u64) -> StdRand {
     StdRand::seed(seed)
 }
+
+fn mix(mut z: u64) -> u64 {
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+    z ^ (z >> 31)
+}
+
+pub fn split(seed: u64, label: &str) -> u64 {
+    split_bytes(seed, label.as_bytes())
+}
+
+pub fn split_bytes(seed: u64, label: &[u8]) -> u64 {
+    let mut h = mix(seed ^ 0x9e3779b97f4a7c15);
+    for chunk in label.chunks(8) {
+        let mut bytes = [0u8; 8];
+        bytes[..chunk.len()].copy_from_slice(chunk);
+        h = mix(h ^ u64::from_le_bytes(bytes));
+    }
+    mix(h ^ label.len() as u64)
+}
+
+#[derive(Clone, Debug)]
+pub struct Permutation {
+    size: u64,
+    half_bits: u32,
+    keys: [u64; 4],
+}
+
+impl Permutation {
+    pub fn new(size: u64, rand: &mut StdRand) -> Permutation {
+        let bits = 64 - size.saturating_sub(1).leading_zeros();
+        Permutation {
+            size,
+            half_bits: bits.div_ceil(2).max(1),
+            keys: [
+                rand.next_u64(),
+                rand.next_u64(),
+                rand.next_u64(),
+                rand.next_u64(),
+            ],
+        }
+    }
+
+    pub fn size(&self) -> u64 {
+        self.size
+    }
+
+    fn encrypt(&self, x: u64) -> u64 {
+        let mask = (1 << self.half_bits) - 1;
+        let (mut l, mut r) = (x >> self.half_bits, x & mask);
+        for key in self.keys {
+            let t = l ^ (mix(r ^ key) & mask);
+            l = r;
+            r = t;
+        }
+        (l << self.half_bits) | r
+    }
+
+    pub fn apply(&self, index: u64) -> u64 {
+        debug_assert!(index < self.size);
+        let mut x = index;
+        loop {
+            x = self.encrypt(x);
+            if x < self.size {
+                return x;
+            }
+        }
+    }
+}
+
+const PHILOX_M0: u32 = 0xD2511F53;
+const PHILOX_M1: u32 = 0xCD9E8D57;
+const PHILOX_W0: u32 = 0x9E3779B9;
+const PHILOX_W1: u32 = 0xBB67AE85;
+
+fn mulhilo(a: u32, b: u32) -> (u32, u32) {
+    let product = a as u64 * b as u64;
+    ((product >> 32) as u32, product as u32)
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Philox {
+    key: [u32; 2],
+    row: u64,
+    draw: u64,
+}
+
+impl Philox {
+    pub fn new(seed: u64) -> Philox {
+        Philox {
+            key: [seed as u32, (seed >> 32) as u32],
+            row: 0,
+            draw: 0,
+        }
+    }
+
+    pub fn row(&self, row: u64) -> Philox {
+        Philox {
+            key: self.key,
+            row,
+            draw: 0,
+        }
+    }
+
+    pub fn block(&self, row: u64, draw: u64) -> [u32; 4] {
+        let mut c = [
+            draw as u32,
+            (draw >> 32) as u32,
+            row as u32,
+            (row >> 32) as u32,
+        ];
+        let mut k = self.key;
+        for _ in 0..10 {
+            let (hi0, lo0) = mulhilo(PHILOX_M0, c[0]);
+            let (hi1, lo1) = mulhilo(PHILOX_M1, c[2]);
+            c = [hi1 ^ c[1] ^ k[0], lo1, hi0 ^ c[3] ^ k[1], lo0];
+            k = [k[0].wrapping_add(PHILOX_W0), k[1].wrapping_add(PHILOX_W1)];
+        }
+        c
+    }
+}
+
+impl Rand for Philox {
+    fn next_u64(&mut self) -> u64 {
+        let block = self.block(self.row, self.draw);
+        self.draw += 1;
+        ((block[0] as u64) << 32) | block[1] as u64
+    }
+}
@@ -0,0 +1,683 @@
+use serde::Deserialize;
+use std::f64::consts::PI;
+use std::io;
+use std::ops::Range;
+use tinyrand::{Rand, RandRange};
+
+fn open01<R: Rand>(rand: &mut R) -> f64 {
+    ((rand.next_u64() >> 11) as f64 + 0.5) / (1u64 << 53) as f64
+}
+
+fn standard_normal<R: Rand>(rand: &mut R) -> f64 {
+    let (u, v) = (open01(rand), open01(rand));
+    (-2.0 * u.ln()).sqrt() * (2.0 * PI * v).cos()
+}
+
+fn gamma<R: Rand>(shape: f64, rand: &mut R) -> f64 {
+    if shape < 1.0 {
+        let u = open01(rand);
+        return gamma(shape + 1.0, rand) * u.powf(1.0 / shape);
+    }
+    let d = shape - 1.0 / 3.0;
+    let c = 1.0 / (9.0 * d).sqrt();
+    loop {
+        let x = standard_normal(rand);
+        let v = 1.0 + c * x;
+        if v <= 0.0 {
+            continue;
+        }
+        let v = v * v * v;
+        let u = open01(rand);
+        if u < 1.0 - 0.0331 * x.powi(4) || u.ln() < 0.5 * x * x + d * (1.0 - v + v.ln()) {
+            return d * v;
+        }
+    }
+}
+
+fn ln_gamma(x: f64) -> f64 {
+    const COEFFICIENTS: [f64; 9] = [
+        0.999_999_999_999_809_9,
+        676.520_368_121_885_1,
+        -1_259.139_216_722_402_8,
+        771.323_428_777_653_1,
+        -176.615_029_162_140_6,
+        12.507_343_278_686_905,
+        -0.138_571_095_265_720_12,
+        9.984_369_578_019_572e-6,
+        1.505_632_735_149_311_6e-7,
+    ];
+    if x < 0.5 {
+        return (PI / (PI * x).sin()).ln() - ln_gamma(1.0 - x);
+    }
+    let x = x - 1.0;
+    let t = x + 7.5;
+    let sum = COEFFICIENTS[1..]
+        .iter()
+        .enumerate()
+        .fold(COEFFICIENTS[0], |sum, (i, c)| {
+            sum + c / (x + i as f64 + 1.0)
+        });
+    0.5 * (2.0 * PI).ln() + (x + 0.5) * t.ln() - t + sum.ln()
+}
+
+pub(crate) fn hypergeometric<R: Rand>(
+    population: u64,
+    successes: u64,
+    draws: u64,
+    rand: &mut R,
+) -> u64 {
+    let low = (successes + draws).saturating_sub(population);
+    let high = successes.min(draws);
+    if low == high {
+        return low;
+    }
+    let (n, k, d) = (population as f64, successes as f64, draws as f64);
+    let ln_choose = |a: f64, b: f64| ln_gamma(a + 1.0) - ln_gamma(b + 1.0) - ln_gamma(a - b + 1.0);
+    let mode = (((d + 1.0) * (k + 1.0) / (n + 2.0)) as u64).clamp(low, high);
+    let m = mode as f64;
+    let p = (ln_choose(k, m) + ln_choose(n - k, d - m) - ln_choose(n, d)).exp();
+    let mut u = open01(rand) - p;
+    let (mut down, mut up, mut p_down, mut p_up) = (mode, mode, p, p);
+    while u > 0.0 && (down > low || up < high) {
+        if up < high {
+            let x = up as f64;
+            p_up *= (k - x) * (d - x) / ((x + 1.0) * (n - k - d + x + 1.0));
+            up += 1;
+            u -= p_up;
+            if u <= 0.0 {
+                return up;
+            }
+        }
+        if down > low {
+            let x = down as f64;
+            p_down *= x * (n - k - d + x) / ((k - x + 1.0) * (d - x + 1.0));
+            down -= 1;
+            u -= p_down;
+            if u <= 0.0 {
+                return down;
+            }
+        }
+    }
+    mode
+}
+
+fn poisson<R: Rand>(lambda: f64, rand: &mut R) -> f64 {
+    if lambda < 10.0 {
+        let limit = (-lambda).exp();
+        let mut k = 0.0;
+        let mut p = open01(rand);
+        while p > limit {
+            k += 1.0;
+            p *= open01(rand);
+        }
+        return k;
+    }
+    let slam = lambda.sqrt();
+    let loglam = lambda.ln();
+    let b = 0.931 + 2.53 * slam;
+    let a = -0.059 + 0.02483 * b;
+    let invalpha = 1.1239 + 1.1328 / (b - 3.4);
+    let vr = 0.9277 - 3.6224 / (b - 2.0);
+    loop {
+        let u = open01(rand) - 0.5;
+        let v = open01(rand);
+        let us = 0.5 - u.abs();
+        let k = ((2.0 * a / us + b) * u + lambda + 0.43).floor();
+        if us >= 0.07 && v <= vr {
+            return k;
+        }
+        if k < 0.0 || (us < 0.013 && v > us) {
+            continue;
+        }
+        if v.ln() + invalpha.ln() - (a / (us * us) + b).ln()
+            <= -lambda + k * loglam - ln_gamma(k + 1.0)
+        {
+            return k;
+        }
+    }
+}
+
+fn erfc(x: f64) -> f64 {
+    let z = x.abs();
+    let t = 1.0 / (1.0 + 0.5 * z);
+    let r = t
+        * (-z * z - 1.265_512_23
+            + t * (1.000_023_68
+                + t * (0.374_091_96
+                    + t * (0.096_784_18
+                        + t * (-0.186_288_06
+                            + t * (0.278_868_07
+                                + t * (-1.135_203_98
+                                    + t * (1.488_515_87
+                                        + t * (-0.822_152_23 + t * 0.170_872_77)))))))))
+            .exp();
+    if x >= 0.0 {
+        r
+    } else {
+        2.0 - r
+    }
+}
+
+fn normal_cdf(x: f64) -> f64 {
+    0.5 * erfc(-x / 2f64.sqrt())
+}
+
+fn normal_quantile(p: f64) -> f64 {
+    const A: [f64; 6] = [
+        -3.969_683_028_665_376e1,
+        2.209_460_984_245_205e2,
+        -2.759_285_104_469_687e2,
+        1.383_577_518_672_69e2,
+        -3.066_479_806_614_716e1,
+        2.506_628_277_459_239,
+    ];
+    const B: [f64; 5] = [
+        -5.447_609_879_822_406e1,
+        1.615_858_368_580_409e2,
+        -1.556_989_798_598_866e2,
+        6.680_131_188_771_972e1,
+        -1.328_068_155_288_572e1,
+    ];
+    const C: [f64; 6] = [
+        -7.784_894_002_430_293e-3,
+        -3.223_964_580_411_365e-1,
+        -2.400_758_277_161_838,
+        -2.549_732_539_343_734,
+        4.374_664_141_464_968,
+        2.938_163_982_698_783,
+    ];
+    const D: [f64; 4] = [
+        7.784_695_709_041_462e-3,
+        3.224_671_290_700_398e-1,
+        2.445_134_137_142_996,
+        3.754_408_661_907_416,
+    ];
+    const LOW: f64 = 0.024_25;
+    if p <= 0.0 {
+        return f64::NEG_INFINITY;
+    }
+    if p >= 1.0 {
+        return f64::INFINITY;
+    }
+    if !(LOW..=1.0 - LOW).contains(&p) {
+        let q = (-2.0 * p.min(1.0 - p).ln()).sqrt();
+        let x = (((((C[0] * q + C[1]) * q + C[2]) * q + C[3]) * q + C[4]) * q + C[5])
+            / ((((D[0] * q + D[1]) * q + D[2]) * q + D[3]) * q + 1.0);
+        return if p < LOW { x } else { -x };
+    }
+    let q = p - 0.5;
+    let r = q * q;
+    (((((A[0] * r + A[1]) * r + A[2]) * r + A[3]) * r + A[4]) * r + A[5]) * q
+        / (((((B[0] * r + B[1]) * r + B[2]) * r + B[3]) * r + B[4]) * r + 1.0)
+}
+
+fn gamma_p(a: f64, x: f64) -> f64 {
+    if x <= 0.0 {
+        return 0.0;
+    }
+    let ln_prefix = a * x.ln() - x - ln_gamma(a);
+    if x < a + 1.0 {
+        let mut term = 1.0 / a;
+        let mut sum = term;
+        for n in 1..1000 {
+            term *= x / (a + n as f64);
+            sum += term;
+            if term.abs() < sum.abs() * 1e-15 {
+                break;
+            }
+        }
+        (sum.ln() + ln_prefix).exp()
+    } else {
+        let fraction = continued_fraction(|n| (-n * (n - a), x + 2.0 * n + 1.0 - a), x + 1.0 - a);
+        1.0 - (fraction.ln() + ln_prefix).exp()
+    }
+}
+
+fn beta_i(a: f64, b: f64, x: f64) -> f64 {
+    if x <= 0.0 {
+        return 0.0;
+    }
+    if x >= 1.0 {
+        return 1.0;
+    }
+    if x > (a + 1.0) / (a + b + 2.0) {
+        return 1.0 - beta_i(b, a, 1.0 - x);
+    }
+    let ln_prefix = ln_gamma(a + b) - ln_gamma(a) - ln_gamma(b) + a * x.ln() + b * (1.0 - x).ln();
+    let fraction = continued_fraction(
+        |n| {
+            let m = (n / 2.0).floor();
+            let numerator = if n % 2.0 == 0.0 {
+                m * (b - m) * x / ((a + 2.0 * m - 1.0) * (a + 2.0 * m))
+            } else {
+                -(a + m) * (a + b + m) * x / ((a + 2.0 * m) * (a + 2.0 * m + 1.0))
+            };
+            (numerator, 1.0)
+        },
+        1.0,
+    );
+    (ln_prefix + fraction.ln()).exp() / a
+}
+
+fn continued_fraction<F: Fn(f64) -> (f64, f64)>(terms: F, b0: f64) -> f64 {
+    const TINY: f64 = 1e-300;
+    let mut c = 1.0 / TINY;
+    let mut d = 1.0 / if b0.abs() < TINY { TINY } else { b0 };
+    let mut h = d;
+    for n in 1..1000 {
+        let (a, b) = terms(n as f64);
+        d = b + a * d;
+        d = 1.0 / if d.abs() < TINY { TINY } else { d };
+        c = b + a / c;
+        if c.abs() < TINY {
+            c = TINY;
+        }
+        let delta = c * d;
+        h *= delta;
+        if (delta - 1.0).abs() < 1e-15 {
+            break;
+        }
+    }
+    h
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Distribution {
+    Uniform { low: f64, high: f64 },
+    Normal { mean: f64, std_dev: f64 },
+    LogNormal { mu: f64, sigma: f64 },
+    Exponential { lambda: f64 },
+    Poisson { lambda: f64 },
+    Geometric { p: f64 },
+    Pareto { scale: f64, shape: f64 },
+    Beta { alpha: f64, beta: f64 },
+}
+
+impl Distribution {
+    pub fn validate(&self) -> io::Result<()> {
+        let valid = match *self {
+            Distribution::Uniform { low, high } => {
+                low.is_finite() && high.is_finite() && low <= high
+            }
+            Distribution::Normal { mean, std_dev } => {
+                mean.is_finite() && std_dev.is_finite() && std_dev >= 0.0
+            }
+            Distribution::LogNormal { mu, sigma } => {
+                mu.is_finite() && sigma.is_finite() && sigma >= 0.0
+            }
+            Distribution::Exponential { lambda } => lambda.is_finite() && lambda > 0.0,
+            Distribution::Poisson { lambda } => lambda.is_finite() && lambda > 0.0,
+            Distribution::Geometric { p } => p > 0.0 && p <= 1.0,
+            Distribution::Pareto { scale, shape } => {
+                scale.is_finite() && shape.is_finite() && scale > 0.0 && shape > 0.0
+            }
+            Distribution::Beta { alpha, beta } => {
+                alpha.is_finite() && beta.is_finite() && alpha > 0.0 && beta > 0.0
+            }
+        };
+        if valid {
+            Ok(())
+        } else {
+            let msg = format!("invalid distribution parameters: {:?}", self);
+            Err(io::Error::new(io::ErrorKind::InvalidInput, msg))
+        }
+    }
+
+    pub fn is_discrete(&self) -> bool {
+        matches!(
+            self,
+            Distribution::Poisson { .. } | Distribution::Geometric { .. }
+        )
+    }
+
+    pub fn mean(&self) -> f64 {
+        match *self {
+            Distribution::Uniform { low, high } => (low + high) / 2.0,
+            Distribution::Normal { mean, .. } => mean,
+            Distribution::LogNormal { mu, sigma } => (mu + sigma * sigma / 2.0).exp(),
+            Distribution::Exponential { lambda } => 1.0 / lambda,
+            Distribution::Poisson { lambda } => lambda,
+            Distribution::Geometric { p } => 1.0 / p,
+            Distribution::Pareto { shape, .. } if shape <= 1.0 => f64::INFINITY,
+            Distribution::Pareto { scale, shape } => shape * scale / (shape - 1.0),
+            Distribution::Beta { alpha, beta } => alpha / (alpha + beta),
+        }
+    }
+
+    pub fn variance(&self) -> f64 {
+        match *self {
+            Distribution::Uniform { low, high } => (high - low).powi(2) / 12.0,
+            Distribution::Normal { std_dev, .. } => std_dev * std_dev,
+            Distribution::LogNormal { mu, sigma } => {
+                (sigma * sigma).exp_m1() * (2.0 * mu + sigma * sigma).exp()
+            }
+            Distribution::Exponential { lambda } => 1.0 / (lambda * lambda),
+            Distribution::Poisson { lambda } => lambda,
+            Distribution::Geometric { p } => (1.0 - p) / (p * p),
+            Distribution::Pareto { shape, .. } if shape <= 2.0 => f64::INFINITY,
+            Distribution::Pareto { scale, shape } => {
+                scale * scale * shape / ((shape - 1.0).powi(2) * (shape - 2.0))
+            }
+            Distribution::Beta { alpha, beta } => {
+                alpha * beta / ((alpha + beta).powi(2) * (alpha + beta + 1.0))
+            }
+        }
+    }
+
+    pub fn cdf(&self, x: f64) -> f64 {
+        match *self {
+            Distribution::Uniform { low, high } if high > low => {
+                ((x - low) / (high - low)).clamp(0.0, 1.0)
+            }
+            Distribution::Uniform { low, .. } => (x >= low) as u8 as f64,
+            Distribution::Normal { mean, std_dev } if std_dev > 0.0 => {
+                normal_cdf((x - mean) / std_dev)
+            }
+            Distribution::Normal { mean, .. } => (x >= mean) as u8 as f64,
+            Distribution::LogNormal { .. } if x <= 0.0 => 0.0,
+            Distribution::LogNormal { mu, sigma } => Distribution::Normal {
+                mean: mu,
+                std_dev: sigma,
+            }
+            .cdf(x.ln()),
+            Distribution::Exponential { .. } if x <= 0.0 => 0.0,
+            Distribution::Exponential { lambda } => -(-lambda * x).exp_m1(),
+            Distribution::Poisson { .. } if x < 0.0 => 0.0,
+            Distribution::Poisson { lambda } => 1.0 - gamma_p(x.floor() + 1.0, lambda),
+            Distribution::Geometric { .. } if x < 1.0 => 0.0,
+            Distribution::Geometric { p } => 1.0 - (1.0 - p).powf(x.floor()),
+            Distribution::Pareto { scale, .. } if x <= scale => 0.0,
+            Distribution::Pareto { scale, shape } => 1.0 - (scale / x).powf(shape),
+            Distribution::Beta { alpha, beta } => beta_i(alpha, beta, x),
+        }
+    }
+
+    pub fn quantile(&self, u: f64) -> f64 {
+        match *self {
+            Distribution::Uniform { low, high } => low + (high - low) * u,
+            Distribution::Normal { mean, std_dev } => mean + std_dev * normal_quantile(u),
+            Distribution::LogNormal { mu, sigma } => (mu + sigma * normal_quantile(u)).exp(),
+            Distribution::Exponential { lambda } => -(-u).ln_1p() / lambda,
+            Distribution::Poisson { lambda } => {
+                let guess = lambda + lambda.sqrt() * normal_quantile(u);
+                let mut k = guess.clamp(0.0, u32::MAX as f64).floor();
+                while self.cdf(k) < u {
+                    k += 1.0;
+                }
+                while k > 0.0 && self.cdf(k - 1.0) >= u {
+                    k -= 1.0;
+                }
+                k
+            }
+            Distribution::Geometric { p } if p >= 1.0 => 1.0,
+            Distribution::Geometric { p } => ((-u).ln_1p() / (1.0 - p).ln()).ceil().max(1.0),
+            Distribution::Pareto { scale, shape } => scale / (1.0 - u).powf(1.0 / shape),
+            Distribution::Beta { .. } => {
+                let (mut low, mut high) = (0.0, 1.0);
+                for _ in 0..64 {
+                    let mid = (low + high) / 2.0;
+                    if self.cdf(mid) < u {
+                        low = mid;
+                    } else {
+                        high = mid;
+                    }
+                }
+                (low + high) / 2.0
+            }
+        }
+    }
+
+    pub fn sample<R: Rand>(&self, rand: &mut R) -> f64 {
+        match *self {
+            Distribution::Uniform { low, high } => low + (high - low) * open01(rand),
+            Distribution::Normal { mean, std_dev } => mean + std_dev * standard_normal(rand),
+            Distribution::LogNormal { mu, sigma } => (mu + sigma * standard_normal(rand)).exp(),
+            Distribution::Exponential { lambda } => -open01(rand).ln() / lambda,
+            Distribution::Poisson { lambda } => poisson(lambda, rand),
+            Distribution::Geometric { p } if p >= 1.0 => 1.0,
+            Distribution::Geometric { p } => (open01(rand).ln() / (1.0 - p).ln()).floor() + 1.0,
+            Distribution::Pareto { scale, shape } => scale / open01(rand).powf(1.0 / shape),
+            Distribution::Beta { alpha, beta } => {
+                let x = gamma(alpha, rand);
+                let y = gamma(beta, rand);
+                x / (x + y)
+            }
+        }
+    }
+}
+
+fn helper1(x: f64) -> f64 {
+    if x.abs() > 1e-8 {
+        x.ln_1p() / x
+    } else {
+        1.0 - x * (0.5 - x * (1.0 / 3.0 - 0.25 * x))
+    }
+}
+
+fn helper2(x: f64) -> f64 {
+    if x.abs() > 1e-8 {
+        x.exp_m1() / x
+    } else {
+        1.0 + x * 0.5 * (1.0 + x / 3.0 * (1.0 + 0.25 * x))
+    }
+}
+
+const ZIPF_TERMS: usize = 32;
+
+fn power_sum(exponent: f64, n: u64) -> f64 {
+    let head = (1..=n.min(ZIPF_TERMS as u64))
+        .map(|i| (i as f64).powf(-exponent))
+        .sum::<f64>();
+    if n <= ZIPF_TERMS as u64 {
+        return head;
+    }
+    let (a, b) = ((ZIPF_TERMS as f64 + 0.5).ln(), (n as f64 + 0.5).ln());
+    let t = 1.0 - exponent;
+    head + helper2(t * b) * b - helper2(t * a) * a
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Zipf {
+    n: u64,
+    exponent: f64,
+    h_integral_x1: f64,
+    h_integral_n: f64,
+    s: f64,
+    prefix: [f64; ZIPF_TERMS + 1],
+    total: f64,
+}
+
+impl Zipf {
+    pub fn new(n: u64, exponent: f64) -> Zipf {
+        let mut zipf = Zipf {
+            n: n.max(1),
+            exponent,
+            h_integral_x1: 0.0,
+            h_integral_n: 0.0,
+            s: 0.0,
+            prefix: [0.0; ZIPF_TERMS + 1],
+            total: 0.0,
+        };
+        for i in 1..=ZIPF_TERMS {
+            zipf.prefix[i] = zipf.prefix[i - 1] + zipf.h(i as f64);
+        }
+        zipf.total = zipf.partial(zipf.n);
+        zipf.h_integral_x1 = zipf.h_integral(1.5) - 1.0;
+        zipf.h_integral_n = zipf.h_integral(zipf.n as f64 + 0.5);
+        zipf.s = 2.0 - zipf.h_integral_inverse(zipf.h_integral(2.5) - zipf.h(2.0));
+        zipf
+    }
+
+    fn h(&self, x: f64) -> f64 {
+        (-self.exponent * x.ln()).exp()
+    }
+
+    fn h_integral(&self, x: f64) -> f64 {
+        let log_x = x.ln();
+        helper2((1.0 - self.exponent) * log_x) * log_x
+    }
+
+    fn h_integral_inverse(&self, x: f64) -> f64 {
+        let t = (x * (1.0 - self.exponent)).max(-1.0);
+        (helper1(t) * x).exp()
+    }
+
+    fn partial(&self, rank: u64) -> f64 {
+        if rank <= ZIPF_TERMS as u64 {
+            self.prefix[rank as usize]
+        } else {
+            let tail =
+                self.h_integral(rank as f64 + 0.5) - self.h_integral(ZIPF_TERMS as f64 + 0.5);
+            self.prefix[ZIPF_TERMS] + tail
+        }
+    }
+
+    pub fn cdf(&self, rank: u64) -> f64 {
+        self.partial(rank.min(self.n)) / self.total
+    }
+
+    pub fn mean_variance(&self) -> (f64, f64) {
+        let mean = power_sum(self.exponent - 1.0, self.n) / self.total;
+        let square = power_sum(self.exponent - 2.0, self.n) / self.total;
+        (mean, square - mean * mean)
+    }
+
+    pub fn sample<R: Rand>(&self, rand: &mut R) -> u64 {
+        loop {
+            let u = self.h_integral_n + open01(rand) * (self.h_integral_x1 - self.h_integral_n);
+            let x = self.h_integral_inverse(u);
+            let k = (x + 0.5).floor().clamp(1.0, self.n as f64);
+            if k - x <= self.s || u >= self.h_integral(k + 0.5) - self.h(k) {
+                return k as u64;
+            }
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Skew {
+    #[default]
+    Uniform,
+    Zipf {
+        exponent: f64,
+    },
+    HotKey {
+        keys: u64,
+        probability: f64,
+    },
+}
+
+impl Skew {
+    pub fn validate(&self) -> io::Result<()> {
+        let valid = match *self {
+            Skew::Uniform => true,
+            Skew::Zipf { exponent } => exponent > 0.0 && exponent.is_finite(),
+            Skew::HotKey { keys, probability } => keys > 0 && (0.0..=1.0).contains(&probability),
+        };
+        if valid {
+            Ok(())
+        } else {
+            let msg = format!("invalid skew parameters: {:?}", self);
+            Err(io::Error::new(io::ErrorKind::InvalidInput, msg))
+        }
+    }
+
+    pub fn sampler(&self, domain: Range<u64>) -> KeySampler {
+        match *self {
+            Skew::Uniform => KeySampler::Uniform(domain),
+            Skew::Zipf { exponent } => {
+                let zipf = Zipf::new(domain.end - domain.start, exponent);
+                KeySampler::Zipf(domain.start, Box::new(zipf))
+            }
+            Skew::HotKey { keys, probability } => KeySampler::HotKey {
+                hot: domain.start..domain.end.min(domain.start + keys),
+                domain,
+                probability,
+            },
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum KeySampler {
+    Uniform(Range<u64>),
+    Zipf(u64, Box<Zipf>),
+    HotKey {
+        hot: Range<u64>,
+        domain: Range<u64>,
+        probability: f64,
+    },
+}
+
+fn uniform_key_mean_variance(domain: &Range<u64>) -> (f64, f64) {
+    let n = (domain.end - domain.start) as f64;
+    (domain.start as f64 + (n - 1.0) / 2.0, (n * n - 1.0) / 12.0)
+}
+
+fn uniform_key_cdf(domain: &Range<u64>, key: u64) -> f64 {
+    if key < domain.start {
+        return 0.0;
+    }
+    let n = domain.end - domain.start;
+    ((key - domain.start + 1).min(n) as f64) / n as f64
+}
+
+impl KeySampler {
+    pub fn mean_variance(&self) -> (f64, f64) {
+        match self {
+            KeySampler::Uniform(domain) => uniform_key_mean_variance(domain),
+            KeySampler::Zipf(start, zipf) => {
+                let (mean, variance) = zipf.mean_variance();
+                (*start as f64 + mean - 1.0, variance)
+            }
+            KeySampler::HotKey {
+                hot,
+                domain,
+                probability,
+            } => {
+                let (hot_mean, hot_variance) = uniform_key_mean_variance(hot);
+                let (mean, variance) = uniform_key_mean_variance(domain);
+                let mixed = probability * hot_mean + (1.0 - probability) * mean;
+                let square = probability * (hot_variance + hot_mean * hot_mean)
+                    + (1.0 - probability) * (variance + mean * mean);
+                (mixed, square - mixed * mixed)
+            }
+        }
+    }
+
+    pub fn cdf(&self, key: u64) -> f64 {
+        match self {
+            KeySampler::Uniform(domain) => uniform_key_cdf(domain, key),
+            KeySampler::Zipf(start, _) if key < *start => 0.0,
+            KeySampler::Zipf(start, zipf) => zipf.cdf(key - start + 1),
+            KeySampler::HotKey {
+                hot,
+                domain,
+                probability,
+            } => {
+                probability * uniform_key_cdf(hot, key)
+                    + (1.0 - probability) * uniform_key_cdf(domain, key)
+            }
+        }
+    }
+
+    pub fn sample<R: Rand>(&self, rand: &mut R) -> u64 {
+        match self {
+            KeySampler::Uniform(domain) => rand.next_range(domain.clone()),
+            KeySampler::Zipf(start, zipf) => start + zipf.sample(rand) - 1,
+            KeySampler::HotKey {
+                hot,
+                domain,
+                probability,
+            } => {
+                if open01(rand) < *probability {
+                    rand.next_range(hot.clone())
+                } else {
+                    rand.next_range(domain.clone())
+                }
+            }
+        }
+    }
+}